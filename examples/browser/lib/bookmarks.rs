@@ -0,0 +1,723 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::store::{self, StoreError};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: usize,
+    pub title: String,
+    pub href: String,
+    pub added: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Folder {
+    pub id: usize,
+    pub name: String,
+    pub children: Vec<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Node {
+    Bookmark(Bookmark),
+    Folder(Folder),
+}
+
+/// A node as listed on the bookmarks page, under the folder it is in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line<'a> {
+    pub depth: usize,
+    /// The folder the node is in, or `None` on the bookmarks bar.
+    pub parent: Option<usize>,
+    pub node: &'a Node,
+}
+
+/// The saved pages, on the bookmarks bar or in folders on it. The whole tree
+/// is saved as one JSON document whenever it changes.
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    path: Option<PathBuf>,
+    /// What is on the bookmarks bar.
+    nodes: Vec<Node>,
+    next_id: usize,
+}
+
+impl Node {
+    pub fn id(&self) -> usize {
+        match self {
+            Node::Bookmark(bookmark) => bookmark.id,
+            Node::Folder(folder) => folder.id,
+        }
+    }
+
+    /// The bookmark's title or the folder's name.
+    pub fn title(&self) -> &str {
+        match self {
+            Node::Bookmark(bookmark) => &bookmark.title,
+            Node::Folder(folder) => &folder.name,
+        }
+    }
+}
+
+impl Bookmarks {
+    pub fn default_path() -> Option<PathBuf> {
+        store::data_path("bookmarks.json")
+    }
+
+    /// An empty bar; without a `path` it is forgotten on exit.
+    pub fn new(path: Option<PathBuf>) -> Bookmarks {
+        Bookmarks {
+            path,
+            nodes: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Picks the bookmarks back up from `path`, empty if nothing was saved
+    /// there yet.
+    pub fn load(path: PathBuf) -> Result<Bookmarks, StoreError> {
+        let nodes: Vec<Node> = store::load(&path)?;
+
+        let next_id = max_id(&nodes).map_or(0, |id| id + 1);
+        Ok(Bookmarks {
+            path: Some(path),
+            nodes,
+            next_id,
+        })
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// The bookmarks right on the bookmarks bar, outside of any folder.
+    pub fn bar(&self) -> impl Iterator<Item = &Bookmark> {
+        self.nodes.iter().filter_map(|node| match node {
+            Node::Bookmark(bookmark) => Some(bookmark),
+            Node::Folder(_) => None,
+        })
+    }
+
+    /// Every bookmark, folders first to last.
+    pub fn all(&self) -> Vec<&Bookmark> {
+        self.lines()
+            .into_iter()
+            .filter_map(|line| match line.node {
+                Node::Bookmark(bookmark) => Some(bookmark),
+                Node::Folder(_) => None,
+            })
+            .collect()
+    }
+
+    /// Every node, each folder followed by what is in it.
+    pub fn lines(&self) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+        push_lines(&mut lines, &self.nodes, 0, None);
+        lines
+    }
+
+    /// Every folder, each with the name to pick it by.
+    pub fn folders(&self) -> Vec<(usize, String)> {
+        self.lines()
+            .into_iter()
+            .filter_map(|line| match line.node {
+                Node::Folder(folder) => Some((folder.id, folder.name.clone())),
+                Node::Bookmark(_) => None,
+            })
+            .collect()
+    }
+
+    /// The id of the bookmark of `href`, if it is bookmarked.
+    pub fn find(&self, href: &str) -> Option<usize> {
+        self.all()
+            .into_iter()
+            .find(|bookmark| bookmark.href == href)
+            .map(|bookmark| bookmark.id)
+    }
+
+    /// Adds a bookmark to the end of the bookmarks bar.
+    pub fn add(
+        &mut self,
+        href: &str,
+        title: &str,
+        added: DateTime<Local>,
+    ) -> Result<usize, StoreError> {
+        let id = self.take_id();
+        self.nodes.push(Node::Bookmark(Bookmark {
+            id,
+            title: title.to_string(),
+            href: href.to_string(),
+            added,
+        }));
+        self.save().map(|()| id)
+    }
+
+    /// Adds an empty folder to the end of the bookmarks bar.
+    pub fn add_folder(&mut self, name: &str) -> Result<usize, StoreError> {
+        let id = self.take_id();
+        self.nodes.push(Node::Folder(Folder {
+            id,
+            name: name.to_string(),
+            children: Vec::new(),
+        }));
+        self.save().map(|()| id)
+    }
+
+    /// Renames the bookmark or folder with `id`.
+    pub fn rename(&mut self, id: usize, title: &str) -> Result<(), StoreError> {
+        if let Some((nodes, index)) = position(&mut self.nodes, id) {
+            match &mut nodes[index] {
+                Node::Bookmark(bookmark) => bookmark.title = title.to_string(),
+                Node::Folder(folder) => folder.name = title.to_string(),
+            }
+        }
+        self.save()
+    }
+
+    /// Removes the bookmark or folder with `id`, and everything in it.
+    pub fn remove(&mut self, id: usize) -> Result<(), StoreError> {
+        if let Some((nodes, index)) = position(&mut self.nodes, id) {
+            nodes.remove(index);
+        }
+        self.save()
+    }
+
+    /// Moves the node with `id` `offset` places down within its folder, or
+    /// up if negative, as far as it can go.
+    pub fn move_by(&mut self, id: usize, offset: isize) -> Result<(), StoreError> {
+        if let Some((nodes, index)) = position(&mut self.nodes, id) {
+            let target = (index as isize + offset).clamp(0, nodes.len() as isize - 1);
+            let node = nodes.remove(index);
+            nodes.insert(target as usize, node);
+        }
+        self.save()
+    }
+
+    /// Moves the node with `id` to the end of `folder`, or of the bookmarks
+    /// bar for `None`. A folder can't be moved into itself.
+    pub fn move_to(&mut self, id: usize, folder: Option<usize>) -> Result<(), StoreError> {
+        if let Some(folder) = folder {
+            let lines = self.lines();
+            let exists = lines
+                .iter()
+                .any(|line| matches!(line.node, Node::Folder(found) if found.id == folder));
+            let inside = lines
+                .iter()
+                .any(|line| line.node.id() == id && contains(line.node, folder));
+            if !exists || inside {
+                return Ok(());
+            }
+        }
+
+        let node = match position(&mut self.nodes, id) {
+            Some((nodes, index)) => nodes.remove(index),
+            None => return Ok(()),
+        };
+        match folder {
+            Some(folder) => children(&mut self.nodes, folder)
+                .expect("the folder is still there")
+                .push(node),
+            None => self.nodes.push(node),
+        }
+        self.save()
+    }
+
+    /// Adds the bookmarks and folders of a Netscape bookmark file to the end
+    /// of the bookmarks bar, returning how many bookmarks there were.
+    pub fn import(&mut self, html: &str) -> Result<usize, StoreError> {
+        let mut nodes = from_html(html, Local::now());
+        let mut count = 0;
+        for node in &mut nodes {
+            count += self.assign_ids(node);
+        }
+        self.nodes.extend(nodes);
+        self.save().map(|()| count)
+    }
+
+    pub fn import_file(&mut self, path: &Path) -> Result<usize, StoreError> {
+        let html = fs::read_to_string(path).map_err(StoreError::Io)?;
+        self.import(&html)
+    }
+
+    pub fn export(&self, path: &Path) -> Result<(), StoreError> {
+        fs::write(path, self.to_html()).map_err(StoreError::Io)
+    }
+
+    /// Writes the bookmarks in the Netscape bookmark file format, which
+    /// other browsers import.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n\
+             <!-- This is an automatically generated file.\n     \
+             It will be read and overwritten.\n     \
+             DO NOT EDIT! -->\n\
+             <META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n\
+             <TITLE>Bookmarks</TITLE>\n\
+             <H1>Bookmarks</H1>\n",
+        );
+        push_html(&mut html, &self.nodes, 0);
+        html
+    }
+
+    fn take_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Gives `node` and everything in it fresh ids, returning how many
+    /// bookmarks there were.
+    fn assign_ids(&mut self, node: &mut Node) -> usize {
+        match node {
+            Node::Bookmark(bookmark) => {
+                bookmark.id = self.take_id();
+                1
+            }
+            Node::Folder(folder) => {
+                folder.id = self.take_id();
+                folder
+                    .children
+                    .iter_mut()
+                    .map(|child| self.assign_ids(child))
+                    .sum()
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), StoreError> {
+        match &self.path {
+            Some(path) => store::save(path, &self.nodes),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The list `id` is in, and where in it.
+fn position(nodes: &mut Vec<Node>, id: usize) -> Option<(&mut Vec<Node>, usize)> {
+    if let Some(index) = nodes.iter().position(|node| node.id() == id) {
+        return Some((nodes, index));
+    }
+    nodes.iter_mut().find_map(|node| match node {
+        Node::Folder(folder) => position(&mut folder.children, id),
+        Node::Bookmark(_) => None,
+    })
+}
+
+/// What is in the folder with `id`.
+fn children(nodes: &mut [Node], id: usize) -> Option<&mut Vec<Node>> {
+    nodes.iter_mut().find_map(|node| match node {
+        Node::Folder(folder) => {
+            if folder.id == id {
+                Some(&mut folder.children)
+            } else {
+                children(&mut folder.children, id)
+            }
+        }
+        Node::Bookmark(_) => None,
+    })
+}
+
+/// Whether `node` is or holds the node with `id`.
+fn contains(node: &Node, id: usize) -> bool {
+    node.id() == id
+        || matches!(node, Node::Folder(folder) if folder.children.iter().any(|child| contains(child, id)))
+}
+
+fn max_id(nodes: &[Node]) -> Option<usize> {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Bookmark(bookmark) => bookmark.id,
+            Node::Folder(folder) => {
+                max_id(&folder.children).map_or(folder.id, |id| id.max(folder.id))
+            }
+        })
+        .max()
+}
+
+fn push_lines<'a>(
+    lines: &mut Vec<Line<'a>>,
+    nodes: &'a [Node],
+    depth: usize,
+    parent: Option<usize>,
+) {
+    for node in nodes {
+        lines.push(Line {
+            depth,
+            parent,
+            node,
+        });
+        if let Node::Folder(folder) = node {
+            push_lines(lines, &folder.children, depth + 1, Some(folder.id));
+        }
+    }
+}
+
+fn push_html(html: &mut String, nodes: &[Node], depth: usize) {
+    let indent = "    ".repeat(depth);
+    html.push_str(&format!("{}<DL><p>\n", indent));
+    for node in nodes {
+        match node {
+            Node::Bookmark(bookmark) => html.push_str(&format!(
+                "{}    <DT><A HREF=\"{}\" ADD_DATE=\"{}\">{}</A>\n",
+                indent,
+                escape(&bookmark.href),
+                bookmark.added.timestamp(),
+                escape(&bookmark.title)
+            )),
+            Node::Folder(folder) => {
+                html.push_str(&format!(
+                    "{}    <DT><H3>{}</H3>\n",
+                    indent,
+                    escape(&folder.name)
+                ));
+                push_html(html, &folder.children, depth + 1);
+            }
+        }
+    }
+    html.push_str(&format!("{}</DL><p>\n", indent));
+}
+
+/// Reads the bookmarks and folders out of a Netscape bookmark file, all with
+/// id 0. Whatever isn't understood is skipped, as every browser writes the
+/// format a little differently. Bookmarks without a date are dated `now`.
+fn from_html(html: &str, now: DateTime<Local>) -> Vec<Node> {
+    let tokens = tokenize(html);
+    Parser {
+        tokens: &tokens,
+        next: 0,
+        now,
+    }
+    .list()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open(String, Vec<(String, String)>),
+    Close(String),
+    Text(String),
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    next: usize,
+    now: DateTime<Local>,
+}
+
+impl<'a> Parser<'a> {
+    /// Reads nodes up to the end of the current `<DL>`.
+    fn list(&mut self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        while let Some(token) = self.advance() {
+            match token {
+                Token::Close(name) if name == "dl" => break,
+                Token::Open(name, attributes) if name == "a" => {
+                    let title = self.text("a");
+                    let href = match attribute(attributes, "href") {
+                        Some(href) => href.to_string(),
+                        None => continue,
+                    };
+                    let added = attribute(attributes, "add_date")
+                        .and_then(|date| date.parse().ok())
+                        .and_then(|seconds| Local.timestamp_opt(seconds, 0).single())
+                        .unwrap_or(self.now);
+                    nodes.push(Node::Bookmark(Bookmark {
+                        id: 0,
+                        title,
+                        href,
+                        added,
+                    }));
+                }
+                Token::Open(name, attributes) if name == "h3" => {
+                    let name = self.text("h3");
+                    let children = if self.enter_list() {
+                        self.list()
+                    } else {
+                        Vec::new()
+                    };
+                    // Other browsers' bookmarks bar goes on ours.
+                    if attribute(attributes, "personal_toolbar_folder") == Some("true") {
+                        nodes.extend(children);
+                    } else {
+                        nodes.push(Node::Folder(Folder {
+                            id: 0,
+                            name,
+                            children,
+                        }));
+                    }
+                }
+                Token::Open(name, _) if name == "dl" => nodes.extend(self.list()),
+                _ => {}
+            }
+        }
+        nodes
+    }
+
+    fn advance(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.next)?;
+        self.next += 1;
+        Some(token)
+    }
+
+    /// The text up to the closing `</name>`.
+    fn text(&mut self, name: &str) -> String {
+        let mut text = String::new();
+        while let Some(token) = self.advance() {
+            match token {
+                Token::Text(part) => text.push_str(part),
+                Token::Close(close) if close == name => break,
+                _ => {}
+            }
+        }
+        text.trim().to_string()
+    }
+
+    /// Steps into the `<DL>` of a folder's contents, if that is what comes
+    /// next.
+    fn enter_list(&mut self) -> bool {
+        while let Some(token) = self.tokens.get(self.next) {
+            match token {
+                Token::Open(name, _) if name == "dl" => {
+                    self.next += 1;
+                    return true;
+                }
+                Token::Text(_) => {}
+                Token::Open(name, _) | Token::Close(name) if name == "p" || name == "dd" => {}
+                _ => return false,
+            }
+            self.next += 1;
+        }
+        false
+    }
+}
+
+fn attribute<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let start = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..start];
+        if !text.trim().is_empty() {
+            tokens.push(Token::Text(unescape(text)));
+        }
+        rest = &rest[start..];
+        if rest.is_empty() {
+            break;
+        }
+
+        // A tag left open at the end of the file runs to the end of it.
+        let tag = match rest.find('>') {
+            Some(end) => {
+                let tag = &rest[1..end];
+                rest = &rest[end + 1..];
+                tag
+            }
+            None => {
+                let tag = &rest[1..];
+                rest = "";
+                tag
+            }
+        };
+        if tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_lowercase()));
+            continue;
+        }
+        let (name, attributes) = match tag.find(char::is_whitespace) {
+            Some(index) => tag.split_at(index),
+            None => (tag, ""),
+        };
+        tokens.push(Token::Open(
+            name.to_lowercase(),
+            parse_attributes(attributes),
+        ));
+    }
+    tokens
+}
+
+/// Reads `NAME="value"` pairs, with the names lowercase.
+fn parse_attributes(source: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = source.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (raw, remaining) = match after.chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => {
+                    let inner = &after[1..];
+                    let close = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..close], inner.get(close + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let close = after.find(char::is_whitespace).unwrap_or(after.len());
+                    after.split_at(close)
+                }
+            };
+            value = unescape(raw);
+            rest = remaining.trim_start();
+        }
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+    }
+    attributes
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::testing::at;
+
+    fn titles(bookmarks: &Bookmarks) -> Vec<(usize, &str)> {
+        bookmarks
+            .lines()
+            .iter()
+            .map(|line| (line.depth, line.node.title()))
+            .collect()
+    }
+
+    fn init() -> Bookmarks {
+        let mut bookmarks = Bookmarks::new(None);
+        bookmarks.add("wabi://about", "About", at(1, 9)).unwrap();
+        let folder = bookmarks.add_folder("Local").unwrap();
+        let tmp = bookmarks.add("file:///tmp/", "tmp", at(2, 9)).unwrap();
+        bookmarks.move_to(tmp, Some(folder)).unwrap();
+        bookmarks
+    }
+
+    #[test]
+    fn test_add_and_find() {
+        let mut bookmarks = init();
+        assert_eq!(
+            titles(&bookmarks),
+            vec![(0, "About"), (0, "Local"), (1, "tmp")]
+        );
+        assert_eq!(bookmarks.bar().count(), 1);
+        assert_eq!(bookmarks.find("file:///tmp/"), Some(2));
+        assert_eq!(bookmarks.find("wabi://history"), None);
+
+        bookmarks.rename(1, "Files").unwrap();
+        bookmarks.remove(0).unwrap();
+        assert_eq!(titles(&bookmarks), vec![(0, "Files"), (1, "tmp")]);
+        assert_eq!(bookmarks.folders(), vec![(1, "Files".to_string())]);
+    }
+
+    #[test]
+    fn test_move() {
+        let mut bookmarks = init();
+        bookmarks.move_by(0, 5).unwrap();
+        assert_eq!(
+            titles(&bookmarks),
+            vec![(0, "Local"), (1, "tmp"), (0, "About")]
+        );
+
+        bookmarks.move_to(1, Some(1)).unwrap();
+        bookmarks.move_to(0, Some(7)).unwrap();
+        assert_eq!(
+            titles(&bookmarks),
+            vec![(0, "Local"), (1, "tmp"), (0, "About")]
+        );
+
+        bookmarks.move_to(2, None).unwrap();
+        bookmarks.move_by(2, -1).unwrap();
+        assert_eq!(
+            titles(&bookmarks),
+            vec![(0, "Local"), (0, "tmp"), (0, "About")]
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let bookmarks = init();
+        let html = bookmarks.to_html();
+        assert!(html.contains("<DT><A HREF=\"wabi://about\" ADD_DATE=\""));
+
+        let mut imported = Bookmarks::new(None);
+        assert_eq!(imported.import(&html).unwrap(), 2);
+        assert_eq!(imported.nodes(), bookmarks.nodes());
+    }
+
+    #[test]
+    fn test_import() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1620000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <DT><A HREF="https://iced.rs/" ADD_DATE="1620000000" ICON="data:x">iced &amp; co</A>
+    </DL><p>
+    <DT><H3>Empty</H3>
+    <dt><a href='file:///tmp/'>tmp</a>
+    <DT><A>No link</A>
+</DL><p>
+"#;
+        let mut bookmarks = Bookmarks::new(None);
+        bookmarks.add("wabi://about", "About", at(1, 9)).unwrap();
+        assert_eq!(bookmarks.import(html).unwrap(), 2);
+        assert_eq!(
+            titles(&bookmarks),
+            vec![(0, "About"), (0, "iced & co"), (0, "Empty"), (0, "tmp")]
+        );
+        assert_eq!(bookmarks.find("https://iced.rs/"), Some(1));
+        assert_eq!(bookmarks.find("file:///tmp/"), Some(3));
+
+        for html in &["<", "<é"] {
+            assert_eq!(Bookmarks::new(None).import(html).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("browser-bookmarks-{}", std::process::id()))
+            .join("bookmarks.json");
+        let _ = fs::remove_file(&path);
+
+        let mut bookmarks = Bookmarks::load(path.clone()).unwrap();
+        bookmarks.add("wabi://about", "About", at(1, 9)).unwrap();
+        bookmarks.add_folder("Local").unwrap();
+
+        let mut loaded = Bookmarks::load(path.clone()).unwrap();
+        assert_eq!(loaded.nodes(), bookmarks.nodes());
+        assert_eq!(loaded.add_folder("More").unwrap(), 2);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
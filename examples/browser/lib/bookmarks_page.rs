@@ -0,0 +1,210 @@
+use std::fmt;
+
+use iced::{
+    button, pick_list, text_input, Align, Column, Element, Length, PickList, Row, Space, Text,
+    TextInput,
+};
+use widgets::{button, icon_button, Size, Theme, Variant};
+
+use crate::bookmarks::{Bookmarks, Node};
+use crate::ProcessMessage;
+
+/// How far each level of folders is indented.
+const INDENT: u16 = 20;
+
+/// The widget state of the `wabi://bookmarks` page, which every tab showing
+/// it shares.
+#[derive(Debug)]
+pub struct BookmarksPage {
+    /// The bookmark file to import from or export to.
+    pub file: String,
+    /// How the last import or export went.
+    pub status: Option<String>,
+    file_state: text_input::State,
+    import_button: button::State,
+    export_button: button::State,
+    new_folder_button: button::State,
+    lines: Vec<LineState>,
+}
+
+/// Where a bookmark or folder can be moved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Destination {
+    /// The folder, or `None` for the bookmarks bar.
+    pub folder: Option<usize>,
+    pub name: String,
+}
+
+#[derive(Debug, Default)]
+struct LineState {
+    title: text_input::State,
+    destination: pick_list::State<Destination>,
+    open_button: button::State,
+    up_button: button::State,
+    down_button: button::State,
+    remove_button: button::State,
+}
+
+impl Default for BookmarksPage {
+    fn default() -> Self {
+        let file = dirs::home_dir()
+            .map(|dir| dir.join("bookmarks.html"))
+            .unwrap_or_else(|| "bookmarks.html".into());
+
+        BookmarksPage {
+            file: file.display().to_string(),
+            status: None,
+            file_state: text_input::State::new(),
+            import_button: button::State::new(),
+            export_button: button::State::new(),
+            new_folder_button: button::State::new(),
+            lines: Vec::new(),
+        }
+    }
+}
+
+impl BookmarksPage {
+    pub fn view(&mut self, bookmarks: &Bookmarks) -> Element<'_, ProcessMessage> {
+        let lines = bookmarks.lines();
+        self.lines.resize_with(lines.len(), LineState::default);
+
+        let mut destinations = vec![Destination {
+            folder: None,
+            name: "Bookmarks bar".to_string(),
+        }];
+        destinations.extend(
+            bookmarks
+                .folders()
+                .into_iter()
+                .map(|(id, name)| Destination {
+                    folder: Some(id),
+                    name,
+                }),
+        );
+
+        let file = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(
+                TextInput::new(
+                    &mut self.file_state,
+                    "Bookmark file...",
+                    &self.file,
+                    ProcessMessage::BookmarksFileChanged,
+                )
+                .padding(10),
+            )
+            .push(
+                button(
+                    &mut self.import_button,
+                    "Import",
+                    Variant::Secondary,
+                    Size::compact(),
+                    Theme::Light,
+                )
+                .on_press(ProcessMessage::ImportBookmarks),
+            )
+            .push(
+                button(
+                    &mut self.export_button,
+                    "Export",
+                    Variant::Secondary,
+                    Size::compact(),
+                    Theme::Light,
+                )
+                .on_press(ProcessMessage::ExportBookmarks),
+            );
+
+        let mut column = Column::new().spacing(10).push(file);
+        if let Some(status) = &self.status {
+            column = column.push(Text::new(status.as_str()).size(16));
+        }
+        column = column.push(
+            button(
+                &mut self.new_folder_button,
+                "New folder",
+                Variant::Primary,
+                Size::compact(),
+                Theme::Light,
+            )
+            .on_press(ProcessMessage::NewFolder),
+        );
+
+        if lines.is_empty() {
+            return column
+                .push(Text::new(
+                    "No bookmarks yet. Press ☆ next to the URL bar to add one.",
+                ))
+                .into();
+        }
+
+        for (line, state) in lines.iter().zip(self.lines.iter_mut()) {
+            let id = line.node.id();
+            let LineState {
+                title,
+                destination,
+                open_button,
+                up_button,
+                down_button,
+                remove_button,
+            } = state;
+
+            let mut row = Row::new()
+                .spacing(10)
+                .align_items(Align::Center)
+                .push(Space::with_width(Length::Units(INDENT * line.depth as u16)))
+                .push(
+                    TextInput::new(title, "Name...", line.node.title(), move |title| {
+                        ProcessMessage::RenameBookmark(id, title)
+                    })
+                    .padding(5)
+                    .width(Length::FillPortion(2)),
+                );
+            row = match line.node {
+                Node::Bookmark(bookmark) => row
+                    .push(
+                        Text::new(bookmark.href.as_str())
+                            .size(14)
+                            .width(Length::FillPortion(2)),
+                    )
+                    .push(
+                        icon_button(open_button, "↗", Theme::Light)
+                            .on_press(ProcessMessage::Open(bookmark.href.clone())),
+                    ),
+                Node::Folder(_) => row.push(Space::with_width(Length::FillPortion(2))),
+            };
+
+            let current = destinations
+                .iter()
+                .find(|destination| destination.folder == line.parent)
+                .cloned();
+            row = row
+                .push(PickList::new(
+                    destination,
+                    destinations.clone(),
+                    current,
+                    move |destination| ProcessMessage::MoveToFolder(id, destination),
+                ))
+                .push(
+                    icon_button(up_button, "↑", Theme::Light)
+                        .on_press(ProcessMessage::MoveBookmark(id, -1)),
+                )
+                .push(
+                    icon_button(down_button, "↓", Theme::Light)
+                        .on_press(ProcessMessage::MoveBookmark(id, 1)),
+                )
+                .push(
+                    icon_button(remove_button, "×", Theme::Light)
+                        .on_press(ProcessMessage::RemoveBookmark(id)),
+                );
+            column = column.push(row);
+        }
+        column.into()
+    }
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -0,0 +1,85 @@
+use iced::{button, text_input, Align, Column, Element, Length, Radio, Row, Text, TextInput};
+use widgets::{button, icon_button, Size, Theme, Variant};
+
+use crate::visits::{TimeRange, Visits};
+use crate::ProcessMessage;
+
+/// The widget state of the `wabi://history` page, which every tab showing it
+/// shares.
+#[derive(Debug, Default)]
+pub struct HistoryPage {
+    pub search: String,
+    pub clear_range: TimeRange,
+    search_state: text_input::State,
+    clear_button: button::State,
+    remove_buttons: Vec<button::State>,
+}
+
+impl HistoryPage {
+    pub fn view(&mut self, visits: &Visits) -> Element<'_, ProcessMessage> {
+        let days = visits.days(&self.search);
+        let entries = days.iter().map(|day| day.entries.len()).sum();
+        self.remove_buttons.resize_with(entries, button::State::new);
+
+        let mut ranges = Row::new().spacing(10).align_items(Align::Center);
+        for range in TimeRange::all().iter() {
+            ranges = ranges.push(Radio::new(
+                *range,
+                range.name(),
+                Some(self.clear_range),
+                ProcessMessage::SelectClearRange,
+            ));
+        }
+        ranges = ranges.push(
+            button(
+                &mut self.clear_button,
+                "Clear browsing data",
+                Variant::Primary,
+                Size::compact(),
+                Theme::Light,
+            )
+            .on_press(ProcessMessage::ClearHistory),
+        );
+
+        let mut column = Column::new()
+            .spacing(10)
+            .push(
+                TextInput::new(
+                    &mut self.search_state,
+                    "Search history...",
+                    &self.search,
+                    ProcessMessage::SearchHistory,
+                )
+                .padding(10),
+            )
+            .push(ranges);
+
+        if days.is_empty() {
+            return column.push(Text::new("No pages visited")).into();
+        }
+
+        let mut remove_buttons = self.remove_buttons.iter_mut();
+        for day in days {
+            column = column.push(Text::new(day.date.format("%A, %B %-d, %Y").to_string()).size(24));
+            for entry in day.entries {
+                let mut label = format!("{} — {}", entry.title, entry.href);
+                if entry.count > 1 {
+                    label.push_str(&format!(" ({} visits)", entry.count));
+                }
+                let remove = remove_buttons.next().expect("a button for every entry");
+                column = column.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Align::Center)
+                        .push(Text::new(entry.last_visit.format("%H:%M").to_string()).size(16))
+                        .push(Text::new(label).size(16).width(Length::Fill))
+                        .push(
+                            icon_button(remove, "×", Theme::Light)
+                                .on_press(ProcessMessage::RemoveVisit(entry.href, day.date)),
+                        ),
+                );
+            }
+        }
+        column.into()
+    }
+}
@@ -0,0 +1,325 @@
+use chrono::{DateTime, Local};
+use iced::button;
+
+use crate::visits::Visit;
+
+/// Where searches go unless configured otherwise; `%s` is replaced by the
+/// query. The browser can't load the web, so it searches what it has: the
+/// history and the bookmarks.
+pub const DEFAULT_SEARCH: &str = "wabi://search?q=%s";
+
+/// How many suggestions are shown under the URL bar at most.
+const SUGGESTION_COUNT: usize = 6;
+
+/// The URL bar's smarts: turning what doesn't look like a URL into a search,
+/// and suggesting pages while typing.
+#[derive(Debug)]
+pub struct Omnibox {
+    search: String,
+    /// What was typed, to go back to once the selection leaves the
+    /// suggestions.
+    typed: String,
+    suggestions: Vec<Suggestion>,
+    selected: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct Suggestion {
+    pub href: String,
+    pub title: String,
+    pub button: button::State,
+}
+
+impl Default for Omnibox {
+    fn default() -> Self {
+        Omnibox::new(DEFAULT_SEARCH)
+    }
+}
+
+impl Omnibox {
+    pub fn new(search: &str) -> Omnibox {
+        Omnibox {
+            search: search.to_string(),
+            typed: String::new(),
+            suggestions: Vec::new(),
+            selected: None,
+        }
+    }
+
+    /// Where pressing Enter on `input` goes: `input` itself if it looks like
+    /// a URL, or a search for it otherwise.
+    pub fn target(&self, input: &str) -> String {
+        if is_search(input) {
+            self.search.replace("%s", &encode_query(input.trim()))
+        } else {
+            input.to_string()
+        }
+    }
+
+    /// Suggests the pages among `candidates` whose URL or title contains
+    /// `input`, the most frequently and recently visited first.
+    pub fn suggest(
+        &mut self,
+        input: &str,
+        candidates: impl IntoIterator<Item = Visit>,
+        now: DateTime<Local>,
+    ) {
+        self.typed = input.to_string();
+        self.selected = None;
+        self.suggestions = rank(input, candidates, now)
+            .into_iter()
+            .take(SUGGESTION_COUNT)
+            .map(|visit| Suggestion {
+                href: visit.href,
+                title: visit.title,
+                button: button::State::new(),
+            })
+            .collect();
+    }
+
+    /// Moves the selection `offset` suggestions down, or up if negative.
+    /// Moving up past the first one goes back to what was typed. Returns
+    /// what the URL bar should now show.
+    pub fn move_selection(&mut self, offset: isize) -> Option<&str> {
+        if self.suggestions.is_empty() {
+            return None;
+        }
+        let last = self.suggestions.len() as isize - 1;
+        let current = self.selected.map_or(-1, |selected| selected as isize);
+        let target = (current + offset).clamp(-1, last);
+        self.selected = if target < 0 {
+            None
+        } else {
+            Some(target as usize)
+        };
+
+        Some(match self.selected {
+            Some(index) => &self.suggestions[index].href,
+            None => &self.typed,
+        })
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn suggestion(&self, index: usize) -> Option<&Suggestion> {
+        self.suggestions.get(index)
+    }
+
+    pub fn suggestions_mut(&mut self) -> &mut [Suggestion] {
+        &mut self.suggestions
+    }
+
+    pub fn dismiss(&mut self) {
+        self.suggestions.clear();
+        self.selected = None;
+    }
+}
+
+/// The pages among `candidates` whose URL or title contains `input`, the
+/// most frequently and recently visited first, with the visits to each page
+/// taken together. Nothing matches blank input.
+pub fn rank(
+    input: &str,
+    candidates: impl IntoIterator<Item = Visit>,
+    now: DateTime<Local>,
+) -> Vec<Visit> {
+    let needle = input.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<Visit> = Vec::new();
+    for candidate in candidates {
+        if !candidate.href.to_lowercase().contains(&needle)
+            && !candidate.title.to_lowercase().contains(&needle)
+        {
+            continue;
+        }
+        match matches
+            .iter_mut()
+            .find(|visit| visit.href == candidate.href)
+        {
+            Some(visit) => visit.times.extend(candidate.times),
+            None => matches.push(candidate),
+        }
+    }
+    matches.sort_by(|a, b| {
+        frecency(b, now)
+            .cmp(&frecency(a, now))
+            .then_with(|| a.href.cmp(&b.href))
+    });
+    matches
+}
+
+/// Whether `input` reads as words to search for rather than a location.
+fn is_search(input: &str) -> bool {
+    let input = input.trim();
+    if input.contains("://") || input.starts_with(&['/', '.', '#', '?'][..]) {
+        return false;
+    }
+    input.contains(char::is_whitespace)
+        || !(input.contains('.') || input.contains(':') || input == "localhost")
+}
+
+/// Percent-encodes `query` for a URL's query string, with `+` for spaces.
+fn encode_query(query: &str) -> String {
+    let mut encoded = String::new();
+    for byte in query.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Reverses `encode_query`, keeping what isn't valid percent-encoding as it
+/// is.
+pub fn decode_query(query: &str) -> String {
+    let bytes = query.as_bytes();
+    let mut decoded = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = query
+            .get(index + 1..index + 3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// How likely `visit` is wanted again: its visits, each weighted by how long
+/// ago it was.
+fn frecency(visit: &Visit, now: DateTime<Local>) -> u32 {
+    visit
+        .times
+        .iter()
+        .map(|time| match (now - *time).num_days() {
+            i64::MIN..=3 => 100,
+            4..=14 => 70,
+            15..=31 => 50,
+            32..=90 => 30,
+            _ => 10,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_std::task::block_on;
+    use chrono::Duration;
+
+    use crate::testing::at;
+    use crate::window::page::{self, PageError};
+
+    fn visit(href: &str, title: &str, count: usize, days_ago: i64) -> Visit {
+        Visit {
+            href: href.to_string(),
+            title: title.to_string(),
+            times: vec![now() - Duration::days(days_ago); count],
+        }
+    }
+
+    fn now() -> DateTime<Local> {
+        at(1, 12)
+    }
+
+    fn hrefs(omnibox: &Omnibox) -> Vec<&str> {
+        omnibox
+            .suggestions
+            .iter()
+            .map(|suggestion| suggestion.href.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_target() {
+        let omnibox = Omnibox::new("https://search.example/?q=%s");
+        assert_eq!(
+            omnibox.target("rust iced & co"),
+            "https://search.example/?q=rust+iced+%26+co"
+        );
+        assert_eq!(omnibox.target("wabi"), "https://search.example/?q=wabi");
+        assert_eq!(omnibox.target("example.com"), "example.com");
+        assert_eq!(omnibox.target("localhost"), "localhost");
+        assert_eq!(omnibox.target("../notes"), "../notes");
+        assert_eq!(omnibox.target("wabi://about"), "wabi://about");
+    }
+
+    #[test]
+    fn test_default_search_loads() {
+        let href = Omnibox::default().target("rust iced");
+        assert_eq!(href, "wabi://search?q=rust+iced");
+        assert!(!matches!(
+            block_on(page::load(href)),
+            Err(PageError::UnsupportedScheme(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_query() {
+        let query = "rust iced & co, ünïcode 100%";
+        assert_eq!(decode_query(&encode_query(query)), query);
+        assert_eq!(decode_query("100%+sure%2"), "100% sure%2");
+    }
+
+    #[test]
+    fn test_suggest() {
+        let mut omnibox = Omnibox::default();
+        omnibox.suggest(
+            "Ab",
+            vec![
+                visit("wabi://about", "About", 1, 0),
+                visit("file:///lab/", "lab", 5, 100),
+                visit("file:///tmp/", "tmp", 9, 0),
+                visit("file:///tabs.txt", "Tabs", 1, 20),
+                visit("wabi://about", "About", 1, 2),
+            ],
+            now(),
+        );
+        assert_eq!(
+            hrefs(&omnibox),
+            vec!["wabi://about", "file:///lab/", "file:///tabs.txt"]
+        );
+
+        omnibox.suggest(" ", vec![visit("wabi://about", "About", 1, 0)], now());
+        assert!(hrefs(&omnibox).is_empty());
+    }
+
+    #[test]
+    fn test_move_selection() {
+        let mut omnibox = Omnibox::default();
+        assert_eq!(omnibox.move_selection(1), None);
+
+        omnibox.suggest(
+            "wabi",
+            vec![
+                visit("wabi://about", "About", 2, 0),
+                visit("wabi://new-tab-page", "New Tab", 1, 0),
+            ],
+            now(),
+        );
+        assert_eq!(omnibox.move_selection(1), Some("wabi://about"));
+        assert_eq!(omnibox.move_selection(1), Some("wabi://new-tab-page"));
+        assert_eq!(omnibox.move_selection(1), Some("wabi://new-tab-page"));
+        assert_eq!(omnibox.selected(), Some(1));
+        assert_eq!(omnibox.move_selection(-5), Some("wabi"));
+        assert_eq!(omnibox.selected(), None);
+    }
+}
@@ -1,23 +1,73 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local, NaiveDate};
 use iced::{
-    button, scrollable, text_input, Background, Button, Color, Column, Container, Element,
-    HorizontalAlignment, Length, Row, Sandbox, Scrollable, Space, Text, TextInput, Vector,
-    VerticalAlignment,
+    button, executor, text_input, Application, Clipboard, Column, Command, Element, Length, Row,
+    Scrollable, Subscription, Text, TextInput,
 };
+use iced_native::keyboard::{self, KeyCode};
+use iced_native::{subscription, Event};
+use widgets::{button, card, icon_button, toolbar, Size, Theme, Variant};
 
-use window::Window;
+use bookmarks_page::{BookmarksPage, Destination};
+use history_page::HistoryPage;
+use omnibox::{Omnibox, DEFAULT_SEARCH};
+use search_page::SearchPage;
+use tabs::{Tab, Tabs};
+use window::{Window, BOOKMARKS_PAGE, HISTORY_PAGE};
 
-use crate::window::location::Location;
+pub use crate::bookmarks::{Bookmark, Bookmarks, Folder, Node};
+pub use crate::store::StoreError;
+pub use crate::visits::{TimeRange, Visit, Visits};
+pub use crate::window::location::Location;
+use crate::window::page::{self, Page, PageError, PageState};
+pub use crate::window::url::{Url, UrlError};
 
+mod bookmarks;
+mod bookmarks_page;
+mod history_page;
+mod omnibox;
+mod search_page;
+mod store;
+mod tabs;
+#[cfg(test)]
+mod testing;
+mod visits;
 mod window;
 
+/// How many characters of a title fit on a tab.
+const TAB_TITLE_LENGTH: usize = 16;
+
 pub struct BrowserProcess {
     back_button: button::State,
     next_button: button::State,
     reload_button: button::State,
-    window: Window,
+    star_button: button::State,
+    new_tab_button: button::State,
+    move_left_button: button::State,
+    move_right_button: button::State,
+    /// One for every bookmark on the bookmarks bar.
+    bar_buttons: Vec<button::State>,
+    all_bookmarks_button: button::State,
+    tabs: Tabs,
+    omnibox: Omnibox,
+    visits: Visits,
+    history_page: HistoryPage,
+    bookmarks: Bookmarks,
+    bookmarks_page: BookmarksPage,
+    search_page: SearchPage,
     debug: bool,
 }
 
+/// How the browser is set up when it starts.
+#[derive(Debug, Clone)]
+pub struct Flags {
+    /// Where input that isn't a URL is searched, with `%s` for the query.
+    pub search: String,
+    pub visits: Visits,
+    pub bookmarks: Bookmarks,
+}
+
 #[derive(Debug, Clone)]
 pub enum ProcessMessage {
     BackPressed,
@@ -25,6 +75,44 @@ pub enum ProcessMessage {
     ReloadPressed,
     InputPath(String),
     Enter,
+    /// A page finished loading in the tab with the given id, and whether it
+    /// counts as a visit.
+    Loaded(usize, String, Result<Page, PageError>, bool),
+    NewTab,
+    CloseTab(usize),
+    SelectTab(usize),
+    MoveTab(isize),
+    /// Moves through the omnibox's suggestions, down or up.
+    MoveSelection(isize),
+    OpenSuggestion(usize),
+    DismissSuggestions,
+    SearchHistory(String),
+    /// Forgets the visits to a page on a day.
+    RemoveVisit(String, NaiveDate),
+    SelectClearRange(TimeRange),
+    ClearHistory,
+    /// Bookmarks the page, or forgets the bookmark if it already was.
+    StarPressed,
+    /// Goes to a page picked from a list, like a bookmark or search result.
+    Open(String),
+    NewFolder,
+    RenameBookmark(usize, String),
+    MoveBookmark(usize, isize),
+    MoveToFolder(usize, Destination),
+    RemoveBookmark(usize),
+    BookmarksFileChanged(String),
+    ImportBookmarks,
+    ExportBookmarks,
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Flags {
+            search: DEFAULT_SEARCH.to_string(),
+            visits: Visits::default(),
+            bookmarks: Bookmarks::default(),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -33,88 +121,496 @@ struct PathInputState {
     state: text_input::State,
 }
 
-impl Sandbox for BrowserProcess {
+impl Application for BrowserProcess {
+    type Executor = executor::Default;
     type Message = ProcessMessage;
+    type Flags = Flags;
 
-    fn new() -> Self {
-        Self {
+    fn new(flags: Flags) -> (Self, Command<Self::Message>) {
+        let mut process = Self {
             back_button: button::State::new(),
             next_button: button::State::new(),
             reload_button: button::State::new(),
-            window: Window::default(),
+            star_button: button::State::new(),
+            new_tab_button: button::State::new(),
+            move_left_button: button::State::new(),
+            move_right_button: button::State::new(),
+            bar_buttons: Vec::new(),
+            all_bookmarks_button: button::State::new(),
+            tabs: Tabs::default(),
+            omnibox: Omnibox::new(&flags.search),
+            visits: flags.visits,
+            history_page: HistoryPage::default(),
+            bookmarks: flags.bookmarks,
+            bookmarks_page: BookmarksPage::default(),
+            search_page: SearchPage::default(),
             debug: false,
-        }
+        };
+        let command = load(process.tabs.active_mut());
+        (process, command)
     }
 
     fn title(&self) -> String {
-        "wabi-sabi".to_string()
+        format!("{} - wabi-sabi", self.tabs.active().title())
     }
 
-    fn update(&mut self, event: Self::Message) {
+    fn update(
+        &mut self,
+        event: Self::Message,
+        _clipboard: &mut Clipboard,
+    ) -> Command<Self::Message> {
+        let tab = self.tabs.active_mut();
+        let window = &mut tab.window;
         match event {
             ProcessMessage::BackPressed => {
-                self.window.history.back();
-                self.window.location.href = self.window.history.path();
-                dbg!(&self.window.location.href);
+                self.omnibox.dismiss();
+                window.history.back();
+                window.follow_history();
+                load(tab)
             }
             ProcessMessage::NextPressed => {
-                self.window.history.forward();
-                self.window.location.href = self.window.history.path();
-                dbg!(&self.window.location.href);
+                self.omnibox.dismiss();
+                window.history.forward();
+                window.follow_history();
+                load(tab)
+            }
+            ProcessMessage::ReloadPressed => {
+                self.omnibox.dismiss();
+                window.follow_history();
+                reload(tab)
             }
             ProcessMessage::InputPath(path) => {
-                self.window.location.href = path;
+                let now = Local::now();
+                self.omnibox
+                    .suggest(&path, candidates(&self.visits, &self.bookmarks, now), now);
+                window.location.input = path;
+                window.location.error = None;
+                Command::none()
+            }
+            ProcessMessage::Enter => submit(tab, &mut self.omnibox),
+            ProcessMessage::Loaded(id, href, result, is_visit) => {
+                // A slow page may finish after the user went somewhere else,
+                // or closed the tab.
+                if let Some(tab) = self.tabs.get_mut(id) {
+                    if href == tab.window.history.path() {
+                        tab.window.page = match result {
+                            Ok(page) => {
+                                // The browser's own pages are not worth
+                                // remembering, and reloads are no new visits.
+                                if is_visit && !href.starts_with("wabi://") {
+                                    if let Err(error) =
+                                        self.visits.record(&href, &page.title, Local::now())
+                                    {
+                                        eprintln!("could not log the visit: {}", error);
+                                    }
+                                }
+                                PageState::Loaded(page)
+                            }
+                            Err(error) => PageState::Failed(error),
+                        };
+                    }
+                }
+                Command::none()
+            }
+            ProcessMessage::NewTab => {
+                self.omnibox.dismiss();
+                load(self.tabs.open())
+            }
+            ProcessMessage::CloseTab(id) => {
+                self.omnibox.dismiss();
+                self.tabs.close(id);
+                Command::none()
+            }
+            ProcessMessage::SelectTab(id) => {
+                self.omnibox.dismiss();
+                self.tabs.select(id);
+                Command::none()
+            }
+            ProcessMessage::MoveTab(offset) => {
+                self.tabs.move_active(offset);
+                Command::none()
+            }
+            ProcessMessage::MoveSelection(offset) => {
+                if let Some(input) = self.omnibox.move_selection(offset) {
+                    window.location.input = input.to_string();
+                }
+                Command::none()
+            }
+            ProcessMessage::OpenSuggestion(index) => {
+                if let Some(suggestion) = self.omnibox.suggestion(index) {
+                    window.location.input = suggestion.href.clone();
+                }
+                submit(tab, &mut self.omnibox)
+            }
+            ProcessMessage::DismissSuggestions => {
+                self.omnibox.dismiss();
+                Command::none()
+            }
+            ProcessMessage::SearchHistory(search) => {
+                self.history_page.search = search;
+                Command::none()
             }
-            ProcessMessage::Enter => {
-                self.window.history.push(&self.window.location.href);
-                println!("Enter!! path is {:?}", &self.window.history);
+            ProcessMessage::RemoveVisit(href, date) => {
+                if let Err(error) = self.visits.remove(&href, date) {
+                    eprintln!("could not forget the visit: {}", error);
+                }
+                Command::none()
+            }
+            ProcessMessage::SelectClearRange(range) => {
+                self.history_page.clear_range = range;
+                Command::none()
+            }
+            ProcessMessage::ClearHistory => {
+                if let Err(error) = self
+                    .visits
+                    .clear(self.history_page.clear_range, Local::now())
+                {
+                    eprintln!("could not clear the browsing data: {}", error);
+                }
+                Command::none()
+            }
+            ProcessMessage::StarPressed => {
+                let href = window.location.href();
+                let result = match self.bookmarks.find(&href) {
+                    Some(id) => self.bookmarks.remove(id),
+                    None => self
+                        .bookmarks
+                        .add(&href, &tab.title(), Local::now())
+                        .map(|_| ()),
+                };
+                report(result);
+                Command::none()
+            }
+            ProcessMessage::Open(href) => {
+                window.location.input = href;
+                submit(tab, &mut self.omnibox)
+            }
+            ProcessMessage::NewFolder => {
+                report(self.bookmarks.add_folder("New folder").map(|_| ()));
+                Command::none()
+            }
+            ProcessMessage::RenameBookmark(id, title) => {
+                report(self.bookmarks.rename(id, &title));
+                Command::none()
+            }
+            ProcessMessage::MoveBookmark(id, offset) => {
+                report(self.bookmarks.move_by(id, offset));
+                Command::none()
+            }
+            ProcessMessage::MoveToFolder(id, destination) => {
+                report(self.bookmarks.move_to(id, destination.folder));
+                Command::none()
+            }
+            ProcessMessage::RemoveBookmark(id) => {
+                report(self.bookmarks.remove(id));
+                Command::none()
+            }
+            ProcessMessage::BookmarksFileChanged(file) => {
+                self.bookmarks_page.file = file;
+                self.bookmarks_page.status = None;
+                Command::none()
+            }
+            ProcessMessage::ImportBookmarks => {
+                let path = PathBuf::from(&self.bookmarks_page.file);
+                self.bookmarks_page.status = Some(match self.bookmarks.import_file(&path) {
+                    Ok(count) => format!("Imported {} bookmarks from {}", count, path.display()),
+                    Err(error) => format!("Could not import: {}", error),
+                });
+                Command::none()
+            }
+            ProcessMessage::ExportBookmarks => {
+                let path = PathBuf::from(&self.bookmarks_page.file);
+                self.bookmarks_page.status = Some(match self.bookmarks.export(&path) {
+                    Ok(()) => format!("Exported to {}", path.display()),
+                    Err(error) => format!("Could not export: {}", error),
+                });
+                Command::none()
             }
-            _ => {}
         }
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        subscription::events_with(|event, _status| match event {
+            // The URL bar captures every key while focused, but has no use
+            // for these.
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => match key_code {
+                KeyCode::Up => Some(ProcessMessage::MoveSelection(-1)),
+                KeyCode::Down => Some(ProcessMessage::MoveSelection(1)),
+                KeyCode::Escape => Some(ProcessMessage::DismissSuggestions),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
     fn view(&mut self) -> Element<'_, Self::Message> {
         let Self {
             back_button,
             next_button,
-            window,
+            reload_button,
+            star_button,
+            new_tab_button,
+            move_left_button,
+            move_right_button,
+            bar_buttons,
+            all_bookmarks_button,
+            tabs,
+            omnibox,
+            visits,
+            history_page,
+            bookmarks,
+            bookmarks_page,
+            search_page,
             ..
         } = self;
+
+        let active = tabs.active_index();
+        let can_close = tabs.can_close();
+        let can_move_left = tabs.can_move(-1);
+        let can_move_right = tabs.can_move(1);
+        let mut strip = Row::new().spacing(5);
+        let mut active_tab = None;
+        for (index, tab) in tabs.iter_mut().enumerate() {
+            let title = tab_title(&tab.title());
+            let Tab {
+                id,
+                window,
+                select_button,
+                close_button,
+                content_scroll,
+            } = tab;
+
+            let variant = if index == active {
+                Variant::Primary
+            } else {
+                Variant::Secondary
+            };
+            let mut close = icon_button(close_button, "×", Theme::Light);
+            if can_close {
+                close = close.on_press(ProcessMessage::CloseTab(*id));
+            }
+            strip = strip.push(
+                Row::new()
+                    .push(
+                        button(
+                            select_button,
+                            &title,
+                            variant,
+                            Size::compact(),
+                            Theme::Light,
+                        )
+                        .on_press(ProcessMessage::SelectTab(*id)),
+                    )
+                    .push(close),
+            );
+
+            if index == active {
+                active_tab = Some((window, content_scroll));
+            }
+        }
+        let (window, content_scroll) = active_tab.expect("the active tab is open");
+
+        let mut move_left = icon_button(move_left_button, "‹", Theme::Light);
+        if can_move_left {
+            move_left = move_left.on_press(ProcessMessage::MoveTab(-1));
+        }
+        let mut move_right = icon_button(move_right_button, "›", Theme::Light);
+        if can_move_right {
+            move_right = move_right.on_press(ProcessMessage::MoveTab(1));
+        }
+        strip = strip
+            .push(icon_button(new_tab_button, "+", Theme::Light).on_press(ProcessMessage::NewTab))
+            .push(move_left)
+            .push(move_right);
+
         let Window {
             history,
             location,
+            page,
         } = window;
 
-        let mut controls = Row::new();
+        let mut controls = toolbar();
 
-        let mut back = button(back_button, "←").style(Buttons::Secondary);
+        let mut back = icon_button(back_button, "←", Theme::Light);
         if !history.no_back() {
             back = back.on_press(ProcessMessage::BackPressed)
         }
-        let mut next = button(next_button, "→").style(Buttons::Secondary);
+        let mut next = icon_button(next_button, "→", Theme::Light);
         if !history.no_next() {
             next = next.on_press(ProcessMessage::NextPressed)
         }
         controls = controls.push(back);
         controls = controls.push(next);
-        let Location { href, state } = location;
-        controls = controls.push(text_input(state, href));
+        controls = controls.push(
+            icon_button(reload_button, "↻", Theme::Light).on_press(ProcessMessage::ReloadPressed),
+        );
+        let star = if bookmarks.find(&location.href()).is_some() {
+            "★"
+        } else {
+            "☆"
+        };
+        let Location {
+            input,
+            error,
+            state,
+            ..
+        } = location;
+        controls = controls.push(text_input(state, input));
+        controls = controls.push(
+            icon_button(star_button, star, Theme::Light).on_press(ProcessMessage::StarPressed),
+        );
+
+        let content: Element<_> = match (page, search_page::terms(&history.path())) {
+            (PageState::Loaded(_), Some(terms)) => {
+                let now = Local::now();
+                let results = omnibox::rank(&terms, candidates(visits, bookmarks, now), now);
+                search_page.view(&terms, results)
+            }
+            (PageState::Loaded(_), None) if history.path() == HISTORY_PAGE => {
+                history_page.view(visits)
+            }
+            (PageState::Loaded(_), None) if history.path() == BOOKMARKS_PAGE => {
+                bookmarks_page.view(bookmarks)
+            }
+            (PageState::Loading, _) => Text::new("Loading...").into(),
+            (PageState::Loaded(page), _) => Text::new(page.body.as_str()).into(),
+            (PageState::Failed(error), _) => {
+                card(Text::new(error.to_string()), Theme::Light).into()
+            }
+        };
 
-        Container::new(controls).into()
+        let mut column = Column::new().push(strip).push(controls);
+        let selected = omnibox.selected();
+        for (index, suggestion) in omnibox.suggestions_mut().iter_mut().enumerate() {
+            let variant = if Some(index) == selected {
+                Variant::Primary
+            } else {
+                Variant::Secondary
+            };
+            let label = format!("{} — {}", suggestion.title, suggestion.href);
+            column = column.push(
+                button(
+                    &mut suggestion.button,
+                    &label,
+                    variant,
+                    Size::compact().width(Length::Fill),
+                    Theme::Light,
+                )
+                .on_press(ProcessMessage::OpenSuggestion(index)),
+            );
+        }
+        if let Some(error) = error {
+            column = column.push(
+                Text::new(error.to_string())
+                    .size(16)
+                    .color(Theme::Light.palette().error),
+            );
+        }
+
+        let bar: Vec<_> = bookmarks.bar().collect();
+        bar_buttons.resize_with(bar.len(), button::State::new);
+        let mut bookmarks_bar = Row::new().spacing(5);
+        for (bookmark, state) in bar.into_iter().zip(bar_buttons.iter_mut()) {
+            bookmarks_bar = bookmarks_bar.push(
+                button(
+                    state,
+                    &tab_title(&bookmark.title),
+                    Variant::Secondary,
+                    Size::compact(),
+                    Theme::Light,
+                )
+                .on_press(ProcessMessage::Open(bookmark.href.clone())),
+            );
+        }
+        bookmarks_bar = bookmarks_bar.push(
+            button(
+                all_bookmarks_button,
+                "All bookmarks",
+                Variant::Secondary,
+                Size::compact(),
+                Theme::Light,
+            )
+            .on_press(ProcessMessage::Open(BOOKMARKS_PAGE.to_string())),
+        );
+
+        column
+            .push(bookmarks_bar)
+            .push(
+                Scrollable::new(content_scroll)
+                    .padding(10)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .push(content),
+            )
+            .into()
     }
 }
 
-fn button<'a, Message: Clone>(state: &'a mut button::State, label: &str) -> Button<'a, Message> {
-    let btn = Button::new(
-        state,
-        Text::new(label)
-            .horizontal_alignment(HorizontalAlignment::Center)
-            .vertical_alignment(VerticalAlignment::Center),
-    )
-    .padding(10)
-    .width(Length::Units(40))
-    .height(Length::Units(30));
-    btn
+/// Starts loading the page the tab's history is at, to be shown once it
+/// arrives.
+fn load(tab: &mut Tab) -> Command<ProcessMessage> {
+    fetch(tab, true)
+}
+
+/// Loads the page the tab is showing again, which isn't another visit.
+fn reload(tab: &mut Tab) -> Command<ProcessMessage> {
+    fetch(tab, false)
+}
+
+fn fetch(tab: &mut Tab, is_visit: bool) -> Command<ProcessMessage> {
+    let id = tab.id;
+    let href = tab.window.history.path();
+    tab.window.page = PageState::Loading;
+    Command::perform(page::load(href.clone()), move |result| {
+        ProcessMessage::Loaded(id, href.clone(), result, is_visit)
+    })
+}
+
+/// Goes to what was typed into the URL bar of `tab`, or searches for it,
+/// unless it makes no URL.
+fn submit(tab: &mut Tab, omnibox: &mut Omnibox) -> Command<ProcessMessage> {
+    omnibox.dismiss();
+    let location = &mut tab.window.location;
+    location.input = omnibox.target(&location.input);
+    if location.submit().is_err() {
+        return Command::none();
+    }
+    let href = location.href();
+    tab.window.history.push(&href);
+    load(tab)
+}
+
+/// Every page the omnibox and the search page can find. A bookmark counts as
+/// a visit just now, so that it ranks with the pages visited lately.
+fn candidates<'a>(
+    visits: &'a Visits,
+    bookmarks: &'a Bookmarks,
+    now: DateTime<Local>,
+) -> impl Iterator<Item = Visit> + 'a {
+    let bookmarked = bookmarks.all().into_iter().map(move |bookmark| Visit {
+        href: bookmark.href.clone(),
+        title: bookmark.title.clone(),
+        times: vec![now],
+    });
+    visits.iter().cloned().chain(bookmarked)
+}
+
+/// Bookmarks are saved on every change; failing that, they still work until
+/// the browser closes.
+fn report(result: Result<(), StoreError>) {
+    if let Err(error) = result {
+        eprintln!("could not save the bookmarks: {}", error);
+    }
+}
+
+/// Shortens `title` to fit on a tab.
+fn tab_title(title: &str) -> String {
+    if title.chars().count() <= TAB_TITLE_LENGTH {
+        return title.to_string();
+    }
+    let mut short: String = title.chars().take(TAB_TITLE_LENGTH - 1).collect();
+    short.push('…');
+    short
 }
 
 fn text_input<'a>(state: &'a mut text_input::State, value: &str) -> Column<'a, ProcessMessage> {
@@ -128,31 +624,3 @@ fn text_input<'a>(state: &'a mut text_input::State, value: &str) -> Column<'a, P
     let txt_inp = Column::new().push(text_input);
     txt_inp
 }
-
-pub enum Buttons {
-    Primary,
-    Secondary,
-}
-
-impl button::StyleSheet for Buttons {
-    fn active(&self) -> button::Style {
-        button::Style {
-            background: Some(Background::Color(match self {
-                Buttons::Primary => Color::from_rgb(0.11, 0.42, 0.87),
-                Buttons::Secondary => Color::from_rgb(0.5, 0.5, 0.5),
-            })),
-            border_radius: 12.0,
-            shadow_offset: Vector::new(1.0, 1.0),
-            text_color: Color::from_rgb8(0xEE, 0xEE, 0xEE),
-            ..button::Style::default()
-        }
-    }
-
-    fn hovered(&self) -> button::Style {
-        button::Style {
-            text_color: Color::WHITE,
-            shadow_offset: Vector::new(1.0, 2.0),
-            ..self.active()
-        }
-    }
-}
@@ -0,0 +1,72 @@
+use iced::{button, Column, Element, Length, Text};
+use widgets::{button, Size, Theme, Variant};
+
+use crate::omnibox::decode_query;
+use crate::visits::Visit;
+use crate::window::SEARCH_PAGE;
+use crate::ProcessMessage;
+
+/// The widget state of the `wabi://search` page, which every tab showing it
+/// shares.
+#[derive(Debug, Default)]
+pub struct SearchPage {
+    open_buttons: Vec<button::State>,
+}
+
+impl SearchPage {
+    /// Lists `results`, the pages found for `terms`.
+    pub fn view(&mut self, terms: &str, results: Vec<Visit>) -> Element<'_, ProcessMessage> {
+        self.open_buttons
+            .resize_with(results.len(), button::State::new);
+
+        let mut column = Column::new().spacing(10);
+        if terms.trim().is_empty() {
+            return column
+                .push(Text::new(
+                    "Type something into the URL bar to search for it.",
+                ))
+                .into();
+        }
+        if results.is_empty() {
+            return column
+                .push(Text::new(format!(
+                    "Nothing in your history or bookmarks matches “{}”.",
+                    terms
+                )))
+                .into();
+        }
+
+        column = column.push(Text::new(format!("Pages matching “{}”", terms)).size(24));
+        for (visit, state) in results.into_iter().zip(self.open_buttons.iter_mut()) {
+            let label = format!("{} — {}", visit.title, visit.href);
+            column = column.push(
+                button(
+                    state,
+                    &label,
+                    Variant::Secondary,
+                    Size::compact().width(Length::Fill),
+                    Theme::Light,
+                )
+                .on_press(ProcessMessage::Open(visit.href)),
+            );
+        }
+        column.into()
+    }
+}
+
+/// What the search page at `href` looks for, or `None` if `href` is some
+/// other page.
+pub fn terms(href: &str) -> Option<String> {
+    let rest = href.strip_prefix(SEARCH_PAGE)?;
+    let rest = rest.split('#').next().unwrap_or("");
+    let query = match rest {
+        "" => "",
+        rest => rest.strip_prefix('?')?,
+    };
+    let terms = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("q="))
+        .map(decode_query)
+        .unwrap_or_default();
+    Some(terms)
+}
@@ -0,0 +1,121 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Where the browser keeps its file called `name`, under the user's data
+/// directory.
+pub fn data_path(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("ice-cube").join(name))
+}
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    Corrupt {
+        /// Only known for files with one JSON value per line; the error of a
+        /// whole JSON file points into it by itself.
+        line: Option<usize>,
+        error: serde_json::Error,
+    },
+}
+
+/// Reads the JSON value at `path`, or the default if there is no file yet.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T, StoreError> {
+    match read(path)? {
+        Some(source) => {
+            serde_json::from_str(&source).map_err(|error| StoreError::Corrupt { line: None, error })
+        }
+        None => Ok(T::default()),
+    }
+}
+
+/// Reads the file at `path` with one JSON value per line, which is empty if
+/// there is no file yet.
+pub fn load_lines<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, StoreError> {
+    let source = read(path)?.unwrap_or_default();
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|error| StoreError::Corrupt {
+                line: Some(index + 1),
+                error,
+            })
+        })
+        .collect()
+}
+
+/// Writes `value` to `path` as pretty JSON.
+pub fn save<T: Serialize>(path: &Path, value: &T) -> Result<(), StoreError> {
+    let json = serde_json::to_string_pretty(value).expect("browser data serializes to JSON");
+    write(path, json)
+}
+
+/// Writes `values` to `path`, one JSON value per line.
+pub fn save_lines<T: Serialize>(path: &Path, values: &[T]) -> Result<(), StoreError> {
+    let mut contents = String::new();
+    for value in values {
+        contents.push_str(&serde_json::to_string(value).expect("browser data serializes to JSON"));
+        contents.push('\n');
+    }
+    write(path, contents)
+}
+
+/// Adds `value` to the end of the file at `path`, on a line of its own.
+pub fn append_line<T: Serialize>(path: &Path, value: &T) -> Result<(), StoreError> {
+    create_dir(path)?;
+    let line = serde_json::to_string(value).expect("browser data serializes to JSON");
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(StoreError::Io)
+}
+
+fn read(path: &Path) -> Result<Option<String>, StoreError> {
+    match fs::read_to_string(path) {
+        Ok(source) => Ok(Some(source)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(StoreError::Io(error)),
+    }
+}
+
+/// Replaces the file at `path` only once `contents` are fully written, so a
+/// crash midway leaves the previous version behind rather than half of each.
+fn write(path: &Path, contents: String) -> Result<(), StoreError> {
+    create_dir(path)?;
+
+    let mut partial = OsString::from(path);
+    partial.push(".partial");
+    fs::write(&partial, contents).map_err(StoreError::Io)?;
+    fs::rename(&partial, path).map_err(StoreError::Io)
+}
+
+fn create_dir(path: &Path) -> Result<(), StoreError> {
+    match path.parent() {
+        Some(dir) => fs::create_dir_all(dir).map_err(StoreError::Io),
+        None => Ok(()),
+    }
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(error) => write!(f, "{}", error),
+            StoreError::Corrupt {
+                line: Some(line),
+                error,
+            } => write!(f, "line {}: {}", line, error),
+            StoreError::Corrupt { line: None, error } => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
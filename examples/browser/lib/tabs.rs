@@ -0,0 +1,199 @@
+use iced::{button, scrollable};
+
+use crate::window::page::PageState;
+use crate::window::Window;
+
+/// A tab, with its own window and the widget state that goes with it.
+#[derive(Debug)]
+pub struct Tab {
+    /// Stays the same while the tab is moved around, so that a page loading
+    /// in the background still finds its tab.
+    pub id: usize,
+    pub window: Window,
+    pub select_button: button::State,
+    pub close_button: button::State,
+    pub content_scroll: scrollable::State,
+}
+
+/// The open tabs, in the order they are shown, and which one is active.
+#[derive(Debug)]
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    active: usize,
+    next_id: usize,
+}
+
+impl Tab {
+    fn new(id: usize) -> Tab {
+        Tab {
+            id,
+            window: Window::default(),
+            select_button: button::State::new(),
+            close_button: button::State::new(),
+            content_scroll: scrollable::State::new(),
+        }
+    }
+
+    /// The title of the page, or its URL until it has loaded.
+    pub fn title(&self) -> String {
+        match &self.window.page {
+            PageState::Loaded(page) => page.title.clone(),
+            _ => self.window.history.path(),
+        }
+    }
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Tabs {
+            tabs: vec![Tab::new(0)],
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl Tabs {
+    /// Opens a tab at the new tab page right after the active one and
+    /// switches to it.
+    pub fn open(&mut self) -> &mut Tab {
+        let tab = Tab::new(self.next_id);
+        self.next_id += 1;
+        self.active += 1;
+        self.tabs.insert(self.active, tab);
+        &mut self.tabs[self.active]
+    }
+
+    /// Closes the tab with `id`, unless it is the last one left. Closing the
+    /// active tab activates the one after it, or before it at the end.
+    pub fn close(&mut self, id: usize) {
+        if !self.can_close() {
+            return;
+        }
+        if let Some(index) = self.position(id) {
+            self.tabs.remove(index);
+            if index < self.active || self.active == self.tabs.len() {
+                self.active -= 1;
+            }
+        }
+    }
+
+    pub fn select(&mut self, id: usize) {
+        if let Some(index) = self.position(id) {
+            self.active = index;
+        }
+    }
+
+    /// Moves the active tab `offset` places to the right, or to the left if
+    /// negative, as far as it can go.
+    pub fn move_active(&mut self, offset: isize) {
+        let target = (self.active as isize + offset).clamp(0, self.tabs.len() as isize - 1);
+        let tab = self.tabs.remove(self.active);
+        self.active = target as usize;
+        self.tabs.insert(self.active, tab);
+    }
+
+    /// Whether the active tab can move `offset` places without falling off
+    /// either end.
+    pub fn can_move(&self, offset: isize) -> bool {
+        let target = self.active as isize + offset;
+        0 <= target && target < self.tabs.len() as isize
+    }
+
+    pub fn active(&self) -> &Tab {
+        &self.tabs[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active]
+    }
+
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut Tab> {
+        self.tabs.iter_mut().find(|tab| tab.id == id)
+    }
+
+    /// Whether there is more than one tab, as the last one stays open.
+    pub fn can_close(&self) -> bool {
+        self.tabs.len() > 1
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Tab> {
+        self.tabs.iter_mut()
+    }
+
+    fn position(&self, id: usize) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(tabs: &Tabs) -> Vec<usize> {
+        tabs.tabs.iter().map(|tab| tab.id).collect()
+    }
+
+    fn init() -> Tabs {
+        let mut tabs = Tabs::default();
+        tabs.open();
+        tabs.open();
+        tabs
+    }
+
+    #[test]
+    fn test_open() {
+        let mut tabs = init();
+        assert_eq!(ids(&tabs), vec![0, 1, 2]);
+        assert_eq!(tabs.active().id, 2);
+
+        tabs.select(0);
+        let tab = tabs.open();
+        assert_eq!(tab.window.history.path(), "wabi://new-tab-page");
+        assert_eq!(ids(&tabs), vec![0, 3, 1, 2]);
+        assert_eq!(tabs.active().id, 3);
+    }
+
+    #[test]
+    fn test_close() {
+        let mut tabs = init();
+        tabs.close(2);
+        assert_eq!(ids(&tabs), vec![0, 1]);
+        assert_eq!(tabs.active().id, 1);
+
+        tabs.select(0);
+        tabs.close(1);
+        assert_eq!(tabs.active().id, 0);
+
+        tabs.close(0);
+        assert_eq!(ids(&tabs), vec![0]);
+    }
+
+    #[test]
+    fn test_close_before_active() {
+        let mut tabs = init();
+        tabs.close(0);
+        assert_eq!(tabs.active().id, 2);
+        assert_eq!(tabs.active_index(), 1);
+    }
+
+    #[test]
+    fn test_move_active() {
+        let mut tabs = init();
+        tabs.move_active(-1);
+        assert_eq!(ids(&tabs), vec![0, 2, 1]);
+        assert_eq!(tabs.active().id, 2);
+
+        assert!(tabs.can_move(-1));
+        assert!(!tabs.can_move(-2));
+        tabs.move_active(-5);
+        assert_eq!(ids(&tabs), vec![2, 0, 1]);
+        assert!(!tabs.can_move(-1));
+        tabs.move_active(1);
+        assert_eq!(ids(&tabs), vec![0, 2, 1]);
+    }
+}
@@ -0,0 +1,6 @@
+use chrono::{DateTime, Local, TimeZone};
+
+/// A fixed time in May 2021, for tests that must not depend on the clock.
+pub fn at(day: u32, hour: u32) -> DateTime<Local> {
+    Local.ymd(2021, 5, day).and_hms(hour, 0, 0)
+}
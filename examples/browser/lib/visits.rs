@@ -0,0 +1,332 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::store::{self, StoreError};
+
+/// A page that was visited, with the time of every visit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Visit {
+    pub href: String,
+    pub title: String,
+    /// Oldest first.
+    pub times: Vec<DateTime<Local>>,
+}
+
+/// The visits to a page on one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub href: String,
+    pub title: String,
+    pub last_visit: DateTime<Local>,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Day {
+    pub date: NaiveDate,
+    /// Latest first.
+    pub entries: Vec<Entry>,
+}
+
+/// How far back clearing the browsing data goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeRange {
+    #[default]
+    LastHour,
+    LastDay,
+    LastWeek,
+    LastFourWeeks,
+    AllTime,
+}
+
+/// The browsing history of every tab. Its file holds a page and visit times
+/// per line: a new visit is appended on a line of its own, and forgetting
+/// visits rewrites the file with one line per page.
+#[derive(Debug, Clone, Default)]
+pub struct Visits {
+    path: Option<PathBuf>,
+    visits: Vec<Visit>,
+}
+
+impl Visit {
+    pub fn count(&self) -> usize {
+        self.times.len()
+    }
+}
+
+impl Visits {
+    pub fn default_path() -> Option<PathBuf> {
+        store::data_path("browser-history.jsonl")
+    }
+
+    /// A blank history; without a `path` it is forgotten on exit.
+    pub fn new(path: Option<PathBuf>) -> Visits {
+        Visits {
+            path,
+            visits: Vec::new(),
+        }
+    }
+
+    /// Picks the history back up from `path`, blank if nothing was saved
+    /// there yet.
+    pub fn load(path: PathBuf) -> Result<Visits, StoreError> {
+        let lines: Vec<Visit> = store::load_lines(&path)?;
+        let mut visits = Visits::new(Some(path));
+        for visit in lines {
+            visits.add(visit);
+        }
+        Ok(visits)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Visit> {
+        self.visits.iter()
+    }
+
+    /// Counts a visit to `href` at `at`, keeping the latest title.
+    pub fn record(
+        &mut self,
+        href: &str,
+        title: &str,
+        at: DateTime<Local>,
+    ) -> Result<(), StoreError> {
+        let visit = Visit {
+            href: href.to_string(),
+            title: title.to_string(),
+            times: vec![at],
+        };
+        let result = match &self.path {
+            Some(path) => store::append_line(path, &visit),
+            None => Ok(()),
+        };
+        self.add(visit);
+        result
+    }
+
+    /// The pages visited on every day, latest first, of those whose URL or
+    /// title contains `search`.
+    pub fn days(&self, search: &str) -> Vec<Day> {
+        let search = search.trim().to_lowercase();
+        let mut days: BTreeMap<NaiveDate, Vec<Entry>> = BTreeMap::new();
+        for visit in &self.visits {
+            if !visit.href.to_lowercase().contains(&search)
+                && !visit.title.to_lowercase().contains(&search)
+            {
+                continue;
+            }
+            for time in &visit.times {
+                let entries = days.entry(time.date().naive_local()).or_default();
+                match entries.iter_mut().find(|entry| entry.href == visit.href) {
+                    Some(entry) => {
+                        entry.last_visit = entry.last_visit.max(*time);
+                        entry.count += 1;
+                    }
+                    None => entries.push(Entry {
+                        href: visit.href.clone(),
+                        title: visit.title.clone(),
+                        last_visit: *time,
+                        count: 1,
+                    }),
+                }
+            }
+        }
+
+        days.into_iter()
+            .rev()
+            .map(|(date, mut entries)| {
+                entries.sort_by_key(|entry| Reverse(entry.last_visit));
+                Day { date, entries }
+            })
+            .collect()
+    }
+
+    /// Forgets the visits to `href` on `date`.
+    pub fn remove(&mut self, href: &str, date: NaiveDate) -> Result<(), StoreError> {
+        self.forget(|visit, time| visit.href == href && time.date().naive_local() == date)
+    }
+
+    /// Forgets every visit within `range` of `now`.
+    pub fn clear(&mut self, range: TimeRange, now: DateTime<Local>) -> Result<(), StoreError> {
+        let start = range.start(now);
+        self.forget(|_, time| !matches!(start, Some(start) if *time < start))
+    }
+
+    fn forget(
+        &mut self,
+        forget: impl Fn(&Visit, &DateTime<Local>) -> bool,
+    ) -> Result<(), StoreError> {
+        for visit in &mut self.visits {
+            let times = std::mem::take(&mut visit.times);
+            visit.times = times
+                .into_iter()
+                .filter(|time| !forget(visit, time))
+                .collect();
+        }
+        self.visits.retain(|visit| !visit.times.is_empty());
+        self.save()
+    }
+
+    /// Merges `visit` into the visits to the same page, whose title it
+    /// replaces.
+    fn add(&mut self, visit: Visit) {
+        let existing = match self
+            .visits
+            .iter_mut()
+            .find(|other| other.href == visit.href)
+        {
+            Some(existing) => existing,
+            None => {
+                self.visits.push(visit);
+                return;
+            }
+        };
+        existing.title = visit.title;
+        for at in visit.times {
+            let index = existing.times.partition_point(|time| *time <= at);
+            existing.times.insert(index, at);
+        }
+    }
+
+    fn save(&self) -> Result<(), StoreError> {
+        match &self.path {
+            Some(path) => store::save_lines(path, &self.visits),
+            None => Ok(()),
+        }
+    }
+}
+
+impl TimeRange {
+    pub fn all() -> [TimeRange; 5] {
+        [
+            TimeRange::LastHour,
+            TimeRange::LastDay,
+            TimeRange::LastWeek,
+            TimeRange::LastFourWeeks,
+            TimeRange::AllTime,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeRange::LastHour => "Last hour",
+            TimeRange::LastDay => "Last 24 hours",
+            TimeRange::LastWeek => "Last 7 days",
+            TimeRange::LastFourWeeks => "Last 4 weeks",
+            TimeRange::AllTime => "All time",
+        }
+    }
+
+    /// When the range begins, counting back from `now`, or `None` for all
+    /// time.
+    pub fn start(self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let length = match self {
+            TimeRange::LastHour => Duration::hours(1),
+            TimeRange::LastDay => Duration::days(1),
+            TimeRange::LastWeek => Duration::weeks(1),
+            TimeRange::LastFourWeeks => Duration::weeks(4),
+            TimeRange::AllTime => return None,
+        };
+        Some(now - length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::testing::at;
+
+    fn init() -> Visits {
+        let mut visits = Visits::new(None);
+        visits.record("wabi://about", "About", at(1, 9)).unwrap();
+        visits.record("file:///tmp/", "tmp", at(1, 10)).unwrap();
+        visits
+            .record("wabi://about", "About wabi-sabi", at(2, 9))
+            .unwrap();
+        visits
+            .record("wabi://about", "About wabi-sabi", at(1, 8))
+            .unwrap();
+        visits
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("browser-history-{}", std::process::id()))
+            .join("history.jsonl");
+        let _ = fs::remove_file(&path);
+
+        let mut visits = Visits::load(path.clone()).unwrap();
+        visits.record("wabi://about", "About", at(1, 9)).unwrap();
+        visits.record("wabi://about", "About", at(2, 9)).unwrap();
+
+        visits.record("file:///tmp/", "tmp", at(1, 10)).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        let loaded = Visits::load(path.clone()).unwrap();
+        let visit = loaded.iter().next().unwrap();
+        assert_eq!(visit.count(), 2);
+        assert_eq!(
+            loaded.iter().collect::<Vec<_>>(),
+            visits.iter().collect::<Vec<_>>()
+        );
+
+        visits
+            .remove("file:///tmp/", NaiveDate::from_ymd_opt(2021, 5, 1).unwrap())
+            .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+        let loaded = Visits::load(path.clone()).unwrap();
+        assert_eq!(
+            loaded.iter().next().unwrap().times,
+            vec![at(1, 9), at(2, 9)]
+        );
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_days() {
+        let visits = init();
+        let about = visits.iter().next().unwrap();
+        assert_eq!(about.title, "About wabi-sabi");
+        assert_eq!(about.times, vec![at(1, 8), at(1, 9), at(2, 9)]);
+
+        let days = visits.days("");
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2021, 5, 2).unwrap());
+        let hrefs: Vec<&str> = days[1]
+            .entries
+            .iter()
+            .map(|entry| entry.href.as_str())
+            .collect();
+        assert_eq!(hrefs, vec!["file:///tmp/", "wabi://about"]);
+        assert_eq!(days[1].entries[1].count, 2);
+
+        let days = visits.days("TMP");
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].entries.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_and_clear() {
+        let mut visits = init();
+        visits
+            .remove("file:///tmp/", NaiveDate::from_ymd_opt(2021, 5, 1).unwrap())
+            .unwrap();
+        assert_eq!(visits.iter().count(), 1);
+
+        visits.clear(TimeRange::LastDay, at(2, 12)).unwrap();
+        assert_eq!(
+            visits.iter().next().unwrap().times,
+            vec![at(1, 8), at(1, 9)]
+        );
+
+        visits.clear(TimeRange::AllTime, at(2, 12)).unwrap();
+        assert_eq!(visits.iter().count(), 0);
+    }
+}
@@ -1,6 +1,8 @@
 use std::cmp::{max, min};
 use std::collections::VecDeque;
 
+use super::NEW_TAB_PAGE;
+
 #[derive(Debug, Clone)]
 pub struct History {
     history: VecDeque<String>,
@@ -10,7 +12,7 @@ pub struct History {
 impl Default for History {
     fn default() -> Self {
         let mut history = VecDeque::new();
-        history.push_back("wabi://new-tab-page".to_string());
+        history.push_back(NEW_TAB_PAGE.to_string());
         Self {
             history,
             current: 1,
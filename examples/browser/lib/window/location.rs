@@ -1,7 +1,115 @@
 use iced::text_input;
 
-#[derive(Debug, Clone, Default)]
+use super::url::{Url, UrlError};
+use super::NEW_TAB_PAGE;
+
+/// Where the window is, with its parts available the way the DOM's
+/// `Location` has them, and what is being typed into the URL bar.
+#[derive(Debug, Clone)]
 pub struct Location {
-    pub href: String,
+    url: Url,
+    /// Only becomes the location once submitted.
+    pub input: String,
+    /// Why the submitted input could not be read as a URL.
+    pub error: Option<UrlError>,
     pub state: text_input::State,
 }
+
+impl Default for Location {
+    fn default() -> Self {
+        Location::new(Url::parse(NEW_TAB_PAGE).expect("the new tab page is a valid URL"))
+    }
+}
+
+impl Location {
+    pub fn new(url: Url) -> Location {
+        Location {
+            input: url.to_string(),
+            url,
+            error: None,
+            state: text_input::State::new(),
+        }
+    }
+
+    /// Goes to `url`, replacing whatever was typed.
+    pub fn set(&mut self, url: Url) {
+        self.input = url.to_string();
+        self.error = None;
+        self.url = url;
+    }
+
+    /// Goes to what was typed, read relative to the current location, or
+    /// keeps the error for the URL bar to show.
+    pub fn submit(&mut self) -> Result<(), UrlError> {
+        match self.url.join(&self.input) {
+            Ok(url) => {
+                self.set(url);
+                Ok(())
+            }
+            Err(error) => {
+                self.error = Some(error.clone());
+                Err(error)
+            }
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn href(&self) -> String {
+        self.url.to_string()
+    }
+
+    pub fn scheme(&self) -> &str {
+        self.url.scheme()
+    }
+
+    pub fn host(&self) -> &str {
+        self.url.host()
+    }
+
+    pub fn port(&self) -> Option<u16> {
+        self.url.port()
+    }
+
+    pub fn path(&self) -> &str {
+        self.url.path()
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.url.query()
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.url.fragment()
+    }
+
+    pub fn origin(&self) -> String {
+        self.url.origin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit() {
+        let mut location = Location::default();
+        assert_eq!(location.host(), "new-tab-page");
+        location.set(Url::parse("file:///tmp/a/b.txt").unwrap());
+        assert_eq!(location.path(), "/tmp/a/b.txt");
+
+        location.input = "../c.txt".to_string();
+        assert_eq!(location.submit(), Ok(()));
+        assert_eq!(location.href(), "file:///tmp/c.txt");
+        assert_eq!(location.input, "file:///tmp/c.txt");
+
+        location.input = "bad host".to_string();
+        assert!(location.submit().is_err());
+        assert!(location.error.is_some());
+        assert_eq!(location.href(), "file:///tmp/c.txt");
+        assert_eq!(location.input, "bad host");
+    }
+}
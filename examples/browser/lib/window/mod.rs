@@ -1,11 +1,34 @@
 pub mod history;
 pub mod location;
+pub mod page;
+pub mod url;
 
 use history::History;
 use location::Location;
+use page::PageState;
+use url::Url;
+
+/// Where every new window starts.
+pub const NEW_TAB_PAGE: &str = "wabi://new-tab-page";
+/// Lists the pages visited, from the omnibox's visit log.
+pub const HISTORY_PAGE: &str = "wabi://history";
+/// Lists and organizes the bookmarks.
+pub const BOOKMARKS_PAGE: &str = "wabi://bookmarks";
+/// Lists the visited and bookmarked pages matching its `q` query.
+pub const SEARCH_PAGE: &str = "wabi://search";
 
 #[derive(Debug, Default, Clone)]
 pub struct Window {
     pub history: History,
     pub location: Location,
+    pub page: PageState,
+}
+
+impl Window {
+    /// Moves the location to where the history is, after going back or
+    /// forward.
+    pub fn follow_history(&mut self) {
+        let url = Url::parse(&self.history.path()).expect("the history only holds valid URLs");
+        self.location.set(url);
+    }
 }
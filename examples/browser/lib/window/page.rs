@@ -0,0 +1,162 @@
+use std::fmt;
+use std::path::Path;
+
+use async_std::fs;
+use async_std::prelude::*;
+
+/// What a loaded page shows in the content area.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum PageState {
+    #[default]
+    Loading,
+    Loaded(Page),
+    Failed(PageError),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageError {
+    NotFound(String),
+    UnsupportedScheme(String),
+    Io(String),
+}
+
+/// Fetches the page at `href`: a local file or directory for `file://`, or
+/// one of the browser's own pages for `wabi://`.
+pub async fn load(href: String) -> Result<Page, PageError> {
+    if let Some(path) = href.strip_prefix("file://") {
+        load_file(Path::new(path)).await
+    } else if let Some(name) = href.strip_prefix("wabi://") {
+        internal(name).ok_or(PageError::NotFound(href))
+    } else {
+        Err(PageError::UnsupportedScheme(href))
+    }
+}
+
+async fn load_file(path: &Path) -> Result<Page, PageError> {
+    let io_error = |error: std::io::Error| match error.kind() {
+        std::io::ErrorKind::NotFound => PageError::NotFound(path.display().to_string()),
+        _ => PageError::Io(format!("{}: {}", path.display(), error)),
+    };
+
+    let title = path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    );
+
+    let metadata = fs::metadata(path).await.map_err(io_error)?;
+    if !metadata.is_dir() {
+        let body = fs::read_to_string(path).await.map_err(io_error)?;
+        return Ok(Page { title, body });
+    }
+
+    let mut names = Vec::new();
+    let mut entries = fs::read_dir(path).await.map_err(io_error)?;
+    while let Some(entry) = entries.next().await {
+        let entry = entry.map_err(io_error)?;
+        let mut name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type().await.map_err(io_error)?.is_dir() {
+            name.push('/');
+        }
+        names.push(name);
+    }
+    names.sort();
+
+    Ok(Page {
+        title,
+        body: names.join("\n"),
+    })
+}
+
+/// The pages built into the browser, by the part after `wabi://`, where a
+/// query is left for the process to read.
+fn internal(name: &str) -> Option<Page> {
+    let name = name.split(&['?', '#'][..]).next().unwrap_or(name);
+    let (title, body) = match name {
+        "new-tab-page" => (
+            "New Tab",
+            "Type a location above and press Enter.\n\n\
+             file:///path/to/file      opens a local file or directory\n\
+             wabi://history            lists the pages you visited\n\
+             wabi://bookmarks          organizes your bookmarks\n\
+             wabi://about              tells you about this browser",
+        ),
+        // Drawn by the process, which has the visits and bookmarks.
+        "history" => ("History", ""),
+        "bookmarks" => ("Bookmarks", ""),
+        "search" => ("Search", ""),
+        "about" => (
+            "About",
+            "wabi-sabi is a small browser built with iced.\n\n\
+             It can open local files and its own wabi:// pages.",
+        ),
+        _ => return None,
+    };
+
+    Some(Page {
+        title: title.to_string(),
+        body: body.to_string(),
+    })
+}
+
+impl fmt::Display for PageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageError::NotFound(href) => write!(f, "{} could not be found", href),
+            PageError::UnsupportedScheme(href) => write!(f, "{} cannot be opened", href),
+            PageError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for PageError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_std::task::block_on;
+
+    #[test]
+    fn test_internal() {
+        let page = block_on(load("wabi://new-tab-page".to_string())).unwrap();
+        assert_eq!(page.title, "New Tab");
+        let page = block_on(load("wabi://search?q=wabi".to_string())).unwrap();
+        assert_eq!(page.title, "Search");
+
+        assert_eq!(
+            block_on(load("wabi://nowhere".to_string())),
+            Err(PageError::NotFound("wabi://nowhere".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_file() {
+        let dir = std::env::temp_dir().join(format!("wabi-page-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("images")).unwrap();
+        std::fs::write(dir.join("index.txt"), "hello").unwrap();
+
+        let href = format!("file://{}", dir.join("index.txt").display());
+        let page = block_on(load(href)).unwrap();
+        assert_eq!(page.title, "index.txt");
+        assert_eq!(page.body, "hello");
+
+        let page = block_on(load(format!("file://{}", dir.display()))).unwrap();
+        assert_eq!(page.body, "images/\nindex.txt");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unsupported() {
+        assert!(matches!(
+            block_on(load("gopher://example.com".to_string())),
+            Err(PageError::UnsupportedScheme(_))
+        ));
+    }
+}
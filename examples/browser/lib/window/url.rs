@@ -0,0 +1,342 @@
+use std::fmt;
+
+/// What a bare host like `example.com` is opened with.
+const DEFAULT_SCHEME: &str = "https";
+
+/// A parsed and normalized URL: the scheme and host are lowercase, default
+/// ports are dropped and `.` and `..` are resolved out of the path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Url {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlError {
+    Empty,
+    InvalidScheme(String),
+    InvalidHost(String),
+    InvalidPort(String),
+}
+
+impl Url {
+    /// Parses an absolute URL such as `file:///tmp` or `wabi://about`.
+    pub fn parse(input: &str) -> Result<Url, UrlError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(UrlError::Empty);
+        }
+        let (scheme, rest) = input
+            .split_once("://")
+            .ok_or_else(|| UrlError::InvalidScheme(input.to_string()))?;
+        if !is_scheme(scheme) {
+            return Err(UrlError::InvalidScheme(scheme.to_string()));
+        }
+        let scheme = scheme.to_ascii_lowercase();
+
+        let (rest, query, fragment) = split_query(rest);
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, "")) => (host, None),
+            Some((host, port)) => (
+                host,
+                Some(
+                    port.parse::<u16>()
+                        .map_err(|_| UrlError::InvalidPort(port.to_string()))?,
+                ),
+            ),
+            None => (authority, None),
+        };
+        if !is_host(host) || (host.is_empty() && scheme != "file") {
+            return Err(UrlError::InvalidHost(host.to_string()));
+        }
+        let port = port.filter(|port| default_port(&scheme) != Some(*port));
+
+        let mut path = normalize_path(path);
+        if path.is_empty() && scheme != "wabi" {
+            path.push('/');
+        }
+
+        Ok(Url {
+            host: host.to_ascii_lowercase(),
+            scheme,
+            port,
+            path,
+            query,
+            fragment,
+        })
+    }
+
+    /// Reads what was typed into the URL bar while at this URL: an absolute
+    /// URL, a path relative to it, a `?query` or `#fragment` on it, or a bare
+    /// host to be opened with the default scheme.
+    pub fn join(&self, input: &str) -> Result<Url, UrlError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(UrlError::Empty);
+        }
+        if input.contains("://") {
+            return Url::parse(input);
+        }
+
+        if let Some(fragment) = input.strip_prefix('#') {
+            return Ok(Url {
+                fragment: Some(fragment.to_string()),
+                ..self.clone()
+            });
+        }
+        if input.starts_with('?') {
+            let (_, query, fragment) = split_query(input);
+            return Ok(Url {
+                query,
+                fragment,
+                ..self.clone()
+            });
+        }
+
+        if input.starts_with('/') || input.starts_with('.') {
+            // Paths only mean something next to a local file; anywhere else
+            // they are taken from the root of the file system.
+            let base = if self.scheme == "file" {
+                self.clone()
+            } else {
+                Url::parse("file:///")?
+            };
+            let (path, query, fragment) = split_query(input);
+            let path = if path.starts_with('/') {
+                path.to_string()
+            } else {
+                let directory = &base.path[..=base.path.rfind('/').unwrap_or(0)];
+                format!("{}{}", directory, path)
+            };
+            return Ok(Url {
+                path: normalize_path(&path),
+                query,
+                fragment,
+                ..base
+            });
+        }
+
+        Url::parse(&format!("{}://{}", DEFAULT_SCHEME, input))
+    }
+
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The port, unless it is the default one of the scheme.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    /// `scheme://host[:port]` for web URLs and `null` for the rest, like the
+    /// opaque origins of the DOM.
+    pub fn origin(&self) -> String {
+        match default_port(&self.scheme) {
+            Some(_) => match self.port {
+                Some(port) => format!("{}://{}:{}", self.scheme, self.host, port),
+                None => format!("{}://{}", self.scheme, self.host),
+            },
+            None => "null".to_string(),
+        }
+    }
+}
+
+fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    matches!(chars.next(), Some(first) if first.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn is_host(host: &str) -> bool {
+    host.chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" => Some(80),
+        "https" => Some(443),
+        _ => None,
+    }
+}
+
+/// Splits `?query` and `#fragment` off the end of `input`.
+fn split_query(input: &str) -> (&str, Option<String>, Option<String>) {
+    let (input, fragment) = match input.split_once('#') {
+        Some((input, fragment)) => (input, Some(fragment.to_string())),
+        None => (input, None),
+    };
+    let (input, query) = match input.split_once('?') {
+        Some((input, query)) => (input, Some(query.to_string())),
+        None => (input, None),
+    };
+    (input, query, fragment)
+}
+
+/// Resolves `.` and `..` out of an absolute path, keeping the trailing slash
+/// they imply.
+fn normalize_path(path: &str) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+    let parts: Vec<&str> = path.split('/').skip(1).collect();
+    let mut segments = Vec::new();
+    for (index, part) in parts.iter().enumerate() {
+        let last = index + 1 == parts.len();
+        match *part {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            part => {
+                segments.push(part);
+                continue;
+            }
+        }
+        if last {
+            segments.push("");
+        }
+    }
+    format!("/{}", segments.join("/"))
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", self.scheme, self.host)?;
+        if let Some(port) = self.port {
+            write!(f, ":{}", port)?;
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(query) = &self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for UrlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlError::Empty => write!(f, "Type a location to go to"),
+            UrlError::InvalidScheme(scheme) => write!(f, "{} is not a valid scheme", scheme),
+            UrlError::InvalidHost(host) if host.is_empty() => write!(f, "The host is missing"),
+            UrlError::InvalidHost(host) => write!(f, "{} is not a valid host", host),
+            UrlError::InvalidPort(port) => write!(f, "{} is not a valid port", port),
+        }
+    }
+}
+
+impl std::error::Error for UrlError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> String {
+        Url::parse(input).unwrap().to_string()
+    }
+
+    fn join(base: &str, input: &str) -> String {
+        Url::parse(base).unwrap().join(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let url = Url::parse("HTTP://Example.COM:8080/a/b?q=1#top").unwrap();
+        assert_eq!(url.scheme(), "http");
+        assert_eq!(url.host(), "example.com");
+        assert_eq!(url.port(), Some(8080));
+        assert_eq!(url.path(), "/a/b");
+        assert_eq!(url.query(), Some("q=1"));
+        assert_eq!(url.fragment(), Some("top"));
+        assert_eq!(url.origin(), "http://example.com:8080");
+
+        let url = Url::parse("wabi://new-tab-page").unwrap();
+        assert_eq!(url.host(), "new-tab-page");
+        assert_eq!(url.path(), "");
+        assert_eq!(url.origin(), "null");
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(parse("https://example.com:443"), "https://example.com/");
+        assert_eq!(parse("file:///tmp/a/../b/./c"), "file:///tmp/b/c");
+        assert_eq!(parse("file:///tmp/a/.."), "file:///tmp/");
+        assert_eq!(parse("file:///.."), "file:///");
+        assert_eq!(parse("  wabi://about  "), "wabi://about");
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(
+            join("file:///tmp/a/b.txt", "./c.txt"),
+            "file:///tmp/a/c.txt"
+        );
+        assert_eq!(join("file:///tmp/a/b.txt", "../c"), "file:///tmp/c");
+        assert_eq!(join("file:///tmp/a/", "/etc"), "file:///etc");
+        assert_eq!(join("wabi://about", "/tmp"), "file:///tmp");
+        assert_eq!(join("wabi://about", "#top"), "wabi://about#top");
+        assert_eq!(join("https://a.com/x?y#z", "?q"), "https://a.com/x?q");
+        assert_eq!(
+            join("wabi://about", "Example.com/x"),
+            "https://example.com/x"
+        );
+        assert_eq!(
+            join("wabi://about", "wabi://new-tab-page"),
+            "wabi://new-tab-page"
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let base = Url::parse("wabi://about").unwrap();
+        assert_eq!(base.join("   "), Err(UrlError::Empty));
+        assert_eq!(
+            base.join("hello world"),
+            Err(UrlError::InvalidHost("hello world".to_string()))
+        );
+        assert_eq!(
+            Url::parse("1http://a.com"),
+            Err(UrlError::InvalidScheme("1http".to_string()))
+        );
+        assert_eq!(
+            Url::parse("http://a.com:99999"),
+            Err(UrlError::InvalidPort("99999".to_string()))
+        );
+        assert_eq!(
+            Url::parse("https:///path"),
+            Err(UrlError::InvalidHost(String::new()))
+        );
+        assert_eq!(
+            Url::parse("example.com"),
+            Err(UrlError::InvalidScheme("example.com".to_string()))
+        );
+    }
+}
@@ -1,9 +1,28 @@
-use iced::{Sandbox, Settings};
+use iced::{Application, Settings};
 
-use process::BrowserProcess;
+use process::{Bookmarks, BrowserProcess, Flags, Visits};
 
 pub fn main() -> iced::Result {
+    let mut flags = Flags::default();
+    if let Some(path) = Visits::default_path() {
+        flags.visits = Visits::load(path.clone()).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path.display(), error);
+            // Keeps what could not be read from being written over.
+            Visits::new(None)
+        });
+    }
+    if let Some(path) = Bookmarks::default_path() {
+        flags.bookmarks = Bookmarks::load(path.clone()).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path.display(), error);
+            Bookmarks::new(None)
+        });
+    }
+    if let Ok(search) = std::env::var("WABI_SEARCH") {
+        flags.search = search;
+    }
+
     BrowserProcess::run(Settings {
+        flags,
         default_font: Some(include_bytes!("../../../fonts/Mamelon-5-Hi-Regular.otf")),
         ..Settings::default()
     })
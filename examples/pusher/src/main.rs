@@ -1,7 +1,5 @@
-use iced::{
-    button, Background, Button, Color, Column, Element, HorizontalAlignment, Row, Sandbox,
-    Settings, Text, Vector,
-};
+use iced::{button, Column, Element, Sandbox, Settings, Text};
+use widgets::{button, Size, Theme, Variant};
 
 fn main() -> iced::Result {
     Pusher::run(Settings::default())
@@ -47,9 +45,14 @@ impl Sandbox for Pusher {
         let mut blocks = Column::new();
 
         blocks = blocks.push(
-            button(btn, "hello")
-                .on_press(Message::Push)
-                .style(Button2::Primary),
+            button(
+                btn,
+                "hello",
+                Variant::Primary,
+                Size::regular(),
+                Theme::Light,
+            )
+            .on_press(Message::Push),
         );
 
         for _ in 0..*block_size {
@@ -58,40 +61,3 @@ impl Sandbox for Pusher {
         blocks.into()
     }
 }
-
-fn button<'a, Message: Clone>(state: &'a mut button::State, label: &str) -> Button<'a, Message> {
-    Button::new(
-        state,
-        Text::new(label).horizontal_alignment(HorizontalAlignment::Center),
-    )
-    .padding(12)
-    .min_width(100)
-}
-
-pub enum Button2 {
-    Primary,
-    Secondary,
-}
-
-impl button::StyleSheet for Button2 {
-    fn active(&self) -> button::Style {
-        button::Style {
-            background: Some(Background::Color(match self {
-                Button2::Primary => Color::from_rgb(0.11, 0.42, 0.87),
-                Button2::Secondary => Color::from_rgb(0.5, 0.5, 0.5),
-            })),
-            border_radius: 12.0,
-            shadow_offset: Vector::new(1.0, 1.0),
-            text_color: Color::from_rgb8(0xEE, 0xEE, 0xEE),
-            ..button::Style::default()
-        }
-    }
-
-    fn hovered(&self) -> button::Style {
-        button::Style {
-            text_color: Color::WHITE,
-            shadow_offset: Vector::new(1.0, 2.0),
-            ..self.active()
-        }
-    }
-}
@@ -0,0 +1,201 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// A list of timed phases run one after the other, like a pomodoro.
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    pub name: String,
+    pub phases: Vec<Phase>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub label: String,
+    pub duration: Duration,
+}
+
+#[derive(Debug)]
+pub enum SequenceError {
+    Io(io::Error),
+    Parse(String),
+    Empty,
+}
+
+/// How a sequence is written down: blocks of phases, each run `repeat` times.
+#[derive(Deserialize)]
+struct SequenceDefinition {
+    name: String,
+    blocks: Vec<Block>,
+}
+
+#[derive(Deserialize)]
+struct Block {
+    #[serde(default = "once")]
+    repeat: usize,
+    phases: Vec<PhaseDefinition>,
+}
+
+#[derive(Deserialize)]
+struct PhaseDefinition {
+    label: String,
+    duration: PhaseDuration,
+}
+
+/// A duration as read by [`parse_duration`].
+#[derive(Deserialize)]
+#[serde(try_from = "String")]
+struct PhaseDuration(Duration);
+
+impl Sequence {
+    /// Four rounds of 25 minutes' work, with 5 minute breaks between them and
+    /// a 15 minute break at the end.
+    pub fn pomodoro() -> Sequence {
+        Sequence::from_toml(include_str!("../sequences/pomodoro.toml"))
+            .expect("the pomodoro sequence is valid")
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Sequence, SequenceError> {
+        let source = fs::read_to_string(path).map_err(SequenceError::Io)?;
+        Sequence::from_toml(&source)
+    }
+
+    pub fn from_toml(source: &str) -> Result<Sequence, SequenceError> {
+        let definition: SequenceDefinition =
+            toml::from_str(source).map_err(|error| SequenceError::Parse(error.to_string()))?;
+
+        let phases: Vec<Phase> = definition
+            .blocks
+            .iter()
+            .flat_map(|block| {
+                block
+                    .phases
+                    .iter()
+                    .cycle()
+                    .take(block.phases.len() * block.repeat)
+            })
+            .map(|phase| Phase {
+                label: phase.label.clone(),
+                duration: phase.duration.0,
+            })
+            .collect();
+
+        if phases.is_empty() {
+            return Err(SequenceError::Empty);
+        }
+        Ok(Sequence {
+            name: definition.name,
+            phases,
+        })
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|phase| phase.duration).sum()
+    }
+
+    /// How long the phases before `index` take altogether.
+    pub fn before(&self, index: usize) -> Duration {
+        self.phases[..index]
+            .iter()
+            .map(|phase| phase.duration)
+            .sum()
+    }
+}
+
+fn once() -> usize {
+    1
+}
+
+impl TryFrom<String> for PhaseDuration {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<PhaseDuration, Self::Error> {
+        parse_duration(&text).map(PhaseDuration)
+    }
+}
+
+/// Reads a duration written in hours, minutes and seconds, e.g. `"25m"`,
+/// `"90s"` or `"1h30m"`.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || {
+        format!(
+            "invalid duration `{}`, expected e.g. `1h30m` or `90s`",
+            text
+        )
+    };
+
+    let mut seconds = 0;
+    let mut number = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(invalid()),
+        };
+        let value: u64 = number.parse().map_err(|_| invalid())?;
+        seconds += value * unit;
+        number.clear();
+    }
+
+    if !number.is_empty() || seconds == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::Io(error) => write!(f, "could not read sequence: {}", error),
+            SequenceError::Parse(error) => write!(f, "invalid sequence: {}", error),
+            SequenceError::Empty => write!(f, "a sequence needs at least one phase"),
+        }
+    }
+}
+
+impl std::error::Error for SequenceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pomodoro() {
+        let pomodoro = Sequence::pomodoro();
+
+        assert_eq!(pomodoro.phases.len(), 8);
+        assert_eq!(pomodoro.phases[1].label, "Short break");
+        assert_eq!(pomodoro.phases[7].label, "Long break");
+        assert_eq!(pomodoro.total(), Duration::from_secs(130 * 60));
+        assert_eq!(pomodoro.before(2), Duration::from_secs(30 * 60));
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert!(parse_duration("25").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("5 min").is_err());
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(matches!(
+            Sequence::from_toml("name = \"Nothing\"\nblocks = []"),
+            Err(SequenceError::Empty)
+        ));
+    }
+}
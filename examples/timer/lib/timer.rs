@@ -0,0 +1,384 @@
+//! The stopwatch, countdown and sequence logic of the timer, free of any UI so
+//! that it can run against a [`ManualClock`] in tests.
+
+use std::time::{Duration, Instant};
+
+pub use crate::clock::{Clock, ManualClock, SystemClock};
+pub use crate::sequence::{parse_duration, Phase, Sequence, SequenceError};
+
+mod clock;
+mod sequence;
+
+pub struct Timer<C: Clock = SystemClock> {
+    clock: C,
+    mode: Mode,
+    tick_state: TickState,
+    /// Time spent ticking, up to the last tick, in the current countdown or
+    /// phase.
+    elapsed: Duration,
+    laps: Vec<Lap>,
+    countdown: Option<Duration>,
+    sequence: Sequence,
+    /// The index of the sequence's current phase.
+    phase: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Stopwatch,
+    Countdown,
+    Sequence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickState {
+    Stopped,
+    Ticking {
+        last_tick: Instant,
+    },
+    /// A countdown or sequence ran out.
+    Finished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lap {
+    /// How long this lap took.
+    pub split: Duration,
+    /// How long all laps up to this one took.
+    pub total: Duration,
+}
+
+/// Something a tick ran into, for which the alarm should go off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A phase of the sequence is over and the next one has begun.
+    PhaseEnded,
+    /// The countdown or the last phase of the sequence is over.
+    Finished,
+}
+
+impl<C: Clock> Timer<C> {
+    pub fn new(clock: C, sequence: Sequence) -> Timer<C> {
+        Timer {
+            clock,
+            mode: Mode::Stopwatch,
+            tick_state: TickState::Stopped,
+            elapsed: Duration::default(),
+            laps: Vec::new(),
+            countdown: None,
+            sequence,
+            phase: 0,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Switches to `mode`, stopping and clearing whatever ran before.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.tick_state = TickState::Stopped;
+        self.elapsed = Duration::default();
+        self.laps.clear();
+        self.phase = 0;
+    }
+
+    pub fn tick_state(&self) -> TickState {
+        self.tick_state
+    }
+
+    pub fn is_ticking(&self) -> bool {
+        matches!(self.tick_state, TickState::Ticking { .. })
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn laps(&self) -> &[Lap] {
+        &self.laps
+    }
+
+    pub fn countdown(&self) -> Option<Duration> {
+        self.countdown
+    }
+
+    /// Sets what the countdown counts down from, where zero means it isn't
+    /// set.
+    pub fn set_countdown(&mut self, countdown: Option<Duration>) {
+        self.countdown = countdown.filter(|countdown| *countdown > Duration::default());
+    }
+
+    pub fn sequence(&self) -> &Sequence {
+        &self.sequence
+    }
+
+    pub fn phase(&self) -> &Phase {
+        &self.sequence.phases[self.phase]
+    }
+
+    pub fn phase_index(&self) -> usize {
+        self.phase
+    }
+
+    /// How long the timer runs before the alarm goes off, if it ever does.
+    pub fn target(&self) -> Option<Duration> {
+        match self.mode {
+            Mode::Stopwatch => None,
+            Mode::Countdown => self.countdown,
+            Mode::Sequence => Some(self.phase().duration),
+        }
+    }
+
+    /// What is left of the countdown or phase.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.target()
+            .map(|target| target.saturating_sub(self.elapsed))
+    }
+
+    /// How long the current run has been going, across all phases of a
+    /// sequence.
+    pub fn run_time(&self) -> Duration {
+        match self.mode {
+            Mode::Sequence => self.sequence.before(self.phase) + self.elapsed,
+            _ => self.elapsed,
+        }
+    }
+
+    /// Whether there is anything to start: a countdown needs a duration and
+    /// a finished timer needs a reset first.
+    pub fn can_start(&self) -> bool {
+        match self.tick_state {
+            TickState::Stopped => self.mode == Mode::Stopwatch || self.target().is_some(),
+            TickState::Ticking { .. } | TickState::Finished => false,
+        }
+    }
+
+    pub fn start(&mut self) {
+        if self.can_start() {
+            self.tick_state = TickState::Ticking {
+                last_tick: self.clock.now(),
+            };
+        }
+    }
+
+    /// Stops ticking, counting the time up to now first, which may be just
+    /// enough to run out.
+    pub fn stop(&mut self) -> Option<Event> {
+        let event = self.tick();
+        if self.is_ticking() {
+            self.tick_state = TickState::Stopped;
+        }
+        event
+    }
+
+    /// Clears the time and laps and goes back to the first phase, without
+    /// stopping the timer if it is running.
+    pub fn reset(&mut self) {
+        self.rewind();
+        self.laps.clear();
+        self.phase = 0;
+    }
+
+    /// Records a lap of the running stopwatch.
+    pub fn lap(&mut self) {
+        if self.mode != Mode::Stopwatch || !self.is_ticking() {
+            return;
+        }
+
+        self.tick();
+        let previous = self
+            .laps
+            .last()
+            .map_or(Duration::default(), |lap| lap.total);
+        self.laps.push(Lap {
+            split: self.elapsed - previous,
+            total: self.elapsed,
+        });
+    }
+
+    /// Moves on to the next phase of the sequence, if there is one.
+    pub fn skip_phase(&mut self) {
+        if self.phase + 1 < self.sequence.phases.len() {
+            self.phase += 1;
+            self.rewind();
+        }
+    }
+
+    pub fn restart_phase(&mut self) {
+        self.rewind();
+    }
+
+    /// Adds the time since the last tick, if the timer is running. A sequence
+    /// moves on to its next phase without stopping, until the last one is
+    /// over.
+    pub fn tick(&mut self) -> Option<Event> {
+        let now = self.clock.now();
+        let last_tick = match &mut self.tick_state {
+            TickState::Ticking { last_tick } => last_tick,
+            _ => return None,
+        };
+        self.elapsed += now.saturating_duration_since(*last_tick);
+        *last_tick = now;
+
+        let target = self.target()?;
+        if self.elapsed < target {
+            return None;
+        }
+
+        if self.mode == Mode::Sequence && self.phase + 1 < self.sequence.phases.len() {
+            self.elapsed -= target;
+            self.phase += 1;
+            Some(Event::PhaseEnded)
+        } else {
+            self.elapsed = target;
+            self.tick_state = TickState::Finished;
+            Some(Event::Finished)
+        }
+    }
+
+    /// Starts the current countdown or phase over, without stopping the timer
+    /// if it is running.
+    fn rewind(&mut self) {
+        self.elapsed = Duration::default();
+        match &mut self.tick_state {
+            TickState::Ticking { last_tick } => *last_tick = self.clock.now(),
+            TickState::Finished => self.tick_state = TickState::Stopped,
+            TickState::Stopped => {}
+        }
+    }
+}
+
+impl Mode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Stopwatch => "Stopwatch",
+            Mode::Countdown => "Countdown",
+            Mode::Sequence => "Sequence",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timer() -> (Timer<ManualClock>, ManualClock) {
+        let clock = ManualClock::new();
+        (Timer::new(clock.clone(), Sequence::pomodoro()), clock)
+    }
+
+    fn seconds(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_start_stop_reset() {
+        let (mut timer, clock) = timer();
+
+        clock.advance(seconds(5));
+        assert_eq!(timer.tick(), None);
+        assert_eq!(timer.elapsed(), Duration::default());
+
+        timer.start();
+        clock.advance(seconds(3));
+        assert_eq!(timer.stop(), None);
+        assert_eq!(timer.elapsed(), seconds(3));
+
+        clock.advance(seconds(10));
+        timer.tick();
+        assert_eq!(timer.elapsed(), seconds(3));
+
+        timer.start();
+        clock.advance(seconds(2));
+        timer.tick();
+        assert_eq!(timer.elapsed(), seconds(5));
+
+        timer.reset();
+        assert!(timer.is_ticking());
+        clock.advance(seconds(1));
+        timer.tick();
+        assert_eq!(timer.elapsed(), seconds(1));
+    }
+
+    #[test]
+    fn test_laps() {
+        let (mut timer, clock) = timer();
+
+        timer.lap();
+        assert!(timer.laps().is_empty());
+
+        timer.start();
+        clock.advance(seconds(4));
+        timer.lap();
+        clock.advance(seconds(6));
+        timer.lap();
+
+        assert_eq!(
+            timer.laps(),
+            &[
+                Lap {
+                    split: seconds(4),
+                    total: seconds(4),
+                },
+                Lap {
+                    split: seconds(6),
+                    total: seconds(10),
+                },
+            ]
+        );
+
+        timer.reset();
+        assert!(timer.laps().is_empty());
+    }
+
+    #[test]
+    fn test_countdown_expires() {
+        let (mut timer, clock) = timer();
+
+        timer.set_mode(Mode::Countdown);
+        assert!(!timer.can_start());
+        timer.set_countdown(Some(seconds(5)));
+        timer.start();
+
+        clock.advance(seconds(3));
+        assert_eq!(timer.tick(), None);
+        assert_eq!(timer.remaining(), Some(seconds(2)));
+
+        clock.advance(seconds(3));
+        assert_eq!(timer.tick(), Some(Event::Finished));
+        assert_eq!(timer.tick_state(), TickState::Finished);
+        assert_eq!(timer.elapsed(), seconds(5));
+
+        clock.advance(seconds(1));
+        assert_eq!(timer.tick(), None);
+        assert!(!timer.can_start());
+
+        timer.reset();
+        assert_eq!(timer.tick_state(), TickState::Stopped);
+        assert!(timer.can_start());
+    }
+
+    #[test]
+    fn test_sequence_advances() {
+        let (mut timer, clock) = timer();
+
+        timer.set_mode(Mode::Sequence);
+        timer.start();
+
+        clock.advance(seconds(26 * 60));
+        assert_eq!(timer.tick(), Some(Event::PhaseEnded));
+        assert_eq!(timer.phase().label, "Short break");
+        assert_eq!(timer.elapsed(), seconds(60));
+        assert!(timer.is_ticking());
+
+        for _ in 0..6 {
+            timer.skip_phase();
+        }
+        assert_eq!(timer.phase().label, "Long break");
+        clock.advance(seconds(20 * 60));
+        assert_eq!(timer.tick(), Some(Event::Finished));
+        assert_eq!(timer.run_time(), timer.sequence().total());
+    }
+}
@@ -0,0 +1,68 @@
+use std::thread;
+use std::time::Duration;
+
+use rodio::source::SineWave;
+use rodio::{OutputStream, Sink, Source};
+
+/// Where the alarm goes off when a countdown finishes.
+pub trait AudioSink: Send {
+    fn play(&mut self);
+}
+
+/// Beeps through the default output device, or rings the terminal bell if
+/// there is none.
+pub struct Speaker;
+
+impl AudioSink for Speaker {
+    fn play(&mut self) {
+        // Opening the device blocks for a moment, and the stream has to live
+        // until the beep is over, so neither can happen on the UI thread.
+        thread::spawn(|| {
+            let result = OutputStream::try_default()
+                .map_err(|error| error.to_string())
+                .and_then(|(_stream, handle)| {
+                    let sink = Sink::try_new(&handle).map_err(|error| error.to_string())?;
+                    for _ in 0..3 {
+                        sink.append(
+                            SineWave::new(880)
+                                .take_duration(Duration::from_millis(200))
+                                .amplify(0.2),
+                        );
+                        sink.append(
+                            SineWave::new(880)
+                                .take_duration(Duration::from_millis(100))
+                                .amplify(0.0),
+                        );
+                    }
+                    sink.sleep_until_end();
+                    Ok(())
+                });
+
+            if let Err(error) = result {
+                eprintln!("could not play the alarm: {}", error);
+                print!("\x07");
+            }
+        });
+    }
+}
+
+/// Counts how often the alarm went off instead of making any noise.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub struct Silent {
+    plays: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[cfg(test)]
+impl Silent {
+    pub fn plays(&self) -> usize {
+        self.plays.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+impl AudioSink for Silent {
+    fn play(&mut self) {
+        self.plays.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
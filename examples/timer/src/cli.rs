@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use timer_core::parse_duration;
+
+use crate::format_duration;
+
+pub const USAGE: &str = "\
+Usage: timer [OPTIONS]
+
+Options:
+    --countdown <DURATION>    Count down from e.g. `5m` or `1h30m`
+    --sequence <FILE>         Run the phases of a sequence file
+    --start                   Start ticking right away
+    --label <LABEL>           Label the session in the history
+    --format <hms|seconds>    Show times as hh:mm:ss.cc or as seconds
+    --window-size <WxH>       Open a window of e.g. `400x440`
+    --always-on-top           Keep the window above others, where supported
+    -h, --help                Print this message
+";
+
+/// What the timer was asked to do on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub countdown: Option<Duration>,
+    pub sequence: Option<PathBuf>,
+    pub start: bool,
+    pub label: Option<String>,
+    pub format: TimeFormat,
+    pub window_size: (u32, u32),
+    pub always_on_top: bool,
+    pub help: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// Hours, minutes, seconds and centiseconds, e.g. "01:02:03.04".
+    Clock,
+    /// Seconds and centiseconds, e.g. "3723.04".
+    Seconds,
+}
+
+impl Options {
+    /// Reads the arguments after the program name. Values follow their flag
+    /// either as the next argument or after an `=`.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.find('=') {
+                Some(index) if arg.starts_with("--") => {
+                    (&arg[..index], Some(arg[index + 1..].to_string()))
+                }
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} needs a value", flag))
+            };
+
+            match flag {
+                "--countdown" => {
+                    let countdown = parse_duration(&value()?)?;
+                    if countdown >= Duration::from_secs(100 * 3600) {
+                        return Err("the countdown has to be shorter than 100 hours".to_string());
+                    }
+                    options.countdown = Some(countdown);
+                }
+                "--sequence" => options.sequence = Some(PathBuf::from(value()?)),
+                "--start" => options.start = true,
+                "--label" => options.label = Some(value()?),
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "hms" => TimeFormat::Clock,
+                        "seconds" => TimeFormat::Seconds,
+                        other => {
+                            return Err(format!(
+                                "unknown format `{}`, expected `hms` or `seconds`",
+                                other
+                            ))
+                        }
+                    }
+                }
+                "--window-size" => options.window_size = parse_size(&value()?)?,
+                "--always-on-top" => options.always_on_top = true,
+                "-h" | "--help" => options.help = true,
+                _ => return Err(format!("unknown argument `{}`", arg)),
+            }
+        }
+
+        if options.countdown.is_some() && options.sequence.is_some() {
+            return Err("--countdown and --sequence cannot be used together".to_string());
+        }
+        Ok(options)
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            countdown: None,
+            sequence: None,
+            start: false,
+            label: None,
+            format: TimeFormat::Clock,
+            window_size: (400, 440),
+            always_on_top: false,
+            help: false,
+        }
+    }
+}
+
+impl TimeFormat {
+    pub fn format(self, duration: Duration) -> String {
+        match self {
+            TimeFormat::Clock => format_duration(duration),
+            TimeFormat::Seconds => format!(
+                "{}.{:02}",
+                duration.as_secs(),
+                duration.subsec_millis() / 10
+            ),
+        }
+    }
+}
+
+fn parse_size(text: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("invalid window size `{}`, expected e.g. `400x440`", text);
+
+    let mut sides = text.splitn(2, 'x').map(str::parse::<u32>);
+    match (sides.next(), sides.next()) {
+        (Some(Ok(width)), Some(Ok(height))) if width > 0 && height > 0 => Ok((width, height)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&[]), Ok(Options::default()));
+
+        let options = parse(&[
+            "--countdown",
+            "5m",
+            "--start",
+            "--label=manual test pass",
+            "--format",
+            "seconds",
+            "--window-size",
+            "300x200",
+            "--always-on-top",
+        ])
+        .unwrap();
+        assert_eq!(options.countdown, Some(Duration::from_secs(300)));
+        assert!(options.start);
+        assert_eq!(options.label.as_deref(), Some("manual test pass"));
+        assert_eq!(options.format, TimeFormat::Seconds);
+        assert_eq!(options.window_size, (300, 200));
+        assert!(options.always_on_top);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["--countdown"]).is_err());
+        assert!(parse(&["--countdown", "100h"]).is_err());
+        assert!(parse(&["--format", "minutes"]).is_err());
+        assert!(parse(&["--window-size", "400"]).is_err());
+        assert!(parse(&["--countdown", "5m", "--sequence", "pomodoro.toml"]).is_err());
+        assert!(parse(&["pomodoro.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_time_format() {
+        let duration = Duration::from_millis(3_723_040);
+
+        assert_eq!(TimeFormat::Clock.format(duration), "01:02:03.04");
+        assert_eq!(TimeFormat::Seconds.format(duration), "3723.04");
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// A finished stopwatch, countdown or sequence run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub started: DateTime<Local>,
+    pub mode: String,
+    #[serde(with = "seconds")]
+    pub duration: Duration,
+    /// The split of every lap, in order.
+    #[serde(with = "seconds::list", default)]
+    pub laps: Vec<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// How much time went into the sessions started on one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day {
+    pub date: NaiveDate,
+    pub sessions: usize,
+    pub total: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+/// Every session logged so far, backed by a file that sessions are only ever
+/// appended to, one JSON object per line.
+#[derive(Debug, Default)]
+pub struct History {
+    path: Option<PathBuf>,
+    sessions: Vec<Session>,
+}
+
+#[derive(Debug)]
+pub enum HistoryError {
+    Io(io::Error),
+    Corrupt {
+        line: usize,
+        error: serde_json::Error,
+    },
+}
+
+impl History {
+    /// Where the history lives, under the user's data directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("ice-cube").join("timer-history.jsonl"))
+    }
+
+    /// An empty history, which is only kept in memory without a `path`.
+    pub fn new(path: Option<PathBuf>) -> History {
+        History {
+            path,
+            sessions: Vec::new(),
+        }
+    }
+
+    /// Reads the sessions logged at `path`, which need not exist yet. Lines
+    /// that don't read as a session are skipped and returned alongside, so
+    /// that one torn write doesn't lose the rest of the history.
+    pub fn load(path: PathBuf) -> Result<(History, Vec<HistoryError>), HistoryError> {
+        let source = match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(HistoryError::Io(error)),
+        };
+
+        let mut sessions = Vec::new();
+        let mut skipped = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(session) => sessions.push(session),
+                Err(error) => skipped.push(HistoryError::Corrupt {
+                    line: index + 1,
+                    error,
+                }),
+            }
+        }
+
+        let history = History {
+            path: Some(path),
+            sessions,
+        };
+        Ok((history, skipped))
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn sessions(&self) -> &[Session] {
+        &self.sessions
+    }
+
+    /// Logs `session`, appending it to the history file if there is one.
+    pub fn record(&mut self, session: Session) -> Result<(), HistoryError> {
+        let result = match &self.path {
+            Some(path) => append(path, &session),
+            None => Ok(()),
+        };
+        self.sessions.push(session);
+        result
+    }
+
+    /// The totals of every day with a session on it, latest first.
+    pub fn days(&self) -> Vec<Day> {
+        let mut days = BTreeMap::new();
+        for session in &self.sessions {
+            let day = days
+                .entry(session.started.date().naive_local())
+                .or_insert((0, Duration::default()));
+            day.0 += 1;
+            day.1 += session.duration;
+        }
+
+        days.into_iter()
+            .rev()
+            .map(|(date, (sessions, total))| Day {
+                date,
+                sessions,
+                total,
+            })
+            .collect()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("started,mode,label,duration,laps\n");
+        for session in &self.sessions {
+            let laps: Vec<String> = session
+                .laps
+                .iter()
+                .map(|lap| format!("{:.2}", lap.as_secs_f64()))
+                .collect();
+
+            csv.push_str(&format!(
+                "{},{},{},{:.2},{}\n",
+                session.started.to_rfc3339(),
+                escape(&session.mode),
+                escape(session.label.as_deref().unwrap_or("")),
+                session.duration.as_secs_f64(),
+                laps.join(";")
+            ));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.sessions).expect("sessions serialize to JSON")
+    }
+
+    pub fn export(&self, format: Format, path: &Path) -> Result<(), HistoryError> {
+        let contents = match format {
+            Format::Csv => self.to_csv(),
+            Format::Json => self.to_json(),
+        };
+        fs::write(path, contents).map_err(HistoryError::Io)
+    }
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+}
+
+fn append(path: &Path, session: &Session) -> Result<(), HistoryError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(HistoryError::Io)?;
+    }
+
+    let line = serde_json::to_string(session).expect("sessions serialize to JSON");
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", line))
+        .map_err(HistoryError::Io)
+}
+
+/// Quotes a CSV field if it would otherwise be misread.
+fn escape(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Durations as fractional seconds, which read better in an export than
+/// serde's seconds and nanoseconds.
+mod seconds {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        f64::deserialize(deserializer).map(Duration::from_secs_f64)
+    }
+
+    pub mod list {
+        use std::time::Duration;
+
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            durations: &[Duration],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(durations.iter().map(Duration::as_secs_f64))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Vec<Duration>, D::Error> {
+            Vec::<f64>::deserialize(deserializer)
+                .map(|seconds| seconds.into_iter().map(Duration::from_secs_f64).collect())
+        }
+    }
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Io(error) => write!(f, "{}", error),
+            HistoryError::Corrupt { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    fn session(day: u32, minutes: u64, label: Option<&str>) -> Session {
+        Session {
+            started: Local.ymd(2021, 5, day).and_hms(9, 0, 0),
+            mode: "Stopwatch".to_string(),
+            duration: Duration::from_secs(minutes * 60),
+            laps: vec![Duration::from_millis(1500)],
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("timer-history-{}", std::process::id()))
+            .join("history.jsonl");
+        let _ = fs::remove_file(&path);
+
+        let (mut history, skipped) = History::load(path.clone()).unwrap();
+        assert!(history.sessions().is_empty());
+        assert!(skipped.is_empty());
+        history.record(session(1, 10, Some("smoke test"))).unwrap();
+        history.record(session(2, 20, None)).unwrap();
+
+        let (loaded, _) = History::load(path.clone()).unwrap();
+        assert_eq!(loaded.sessions(), history.sessions());
+
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{{\"started\":"))
+            .unwrap();
+        history.record(session(3, 5, None)).unwrap();
+        let (loaded, skipped) = History::load(path.clone()).unwrap();
+        assert_eq!(loaded.sessions(), history.sessions());
+        assert!(matches!(
+            skipped[..],
+            [HistoryError::Corrupt { line: 3, .. }]
+        ));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_days() {
+        let mut history = History::new(None);
+        for session in [
+            session(1, 10, None),
+            session(2, 5, None),
+            session(1, 15, None),
+        ] {
+            history.record(session).unwrap();
+        }
+
+        let days = history.days();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2021, 5, 2).unwrap());
+        assert_eq!(days[1].sessions, 2);
+        assert_eq!(days[1].total, Duration::from_secs(25 * 60));
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut history = History::new(None);
+        history
+            .record(session(1, 1, Some("pass 2, \"quick\"")))
+            .unwrap();
+
+        let csv = history.to_csv();
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.ends_with(",Stopwatch,\"pass 2, \"\"quick\"\"\",60.00,1.50"));
+    }
+}
@@ -1,18 +1,99 @@
+use std::env;
+use std::process;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
 use iced::{
-    button, executor, Align, Application, Button, Clipboard, Column, Command, Element, Executor,
-    Font, HorizontalAlignment, Length, Row, Settings, Text,
+    button, executor, scrollable, text_input, time, Align, Application, Button, Clipboard, Color,
+    Column, Command, Element, HorizontalAlignment, Length, ProgressBar, Radio, Row, Scrollable,
+    Settings, Subscription, Text, TextInput,
 };
 
-fn main() {
-    let mut settings = Settings::default();
-    settings.window.size = (400u32, 120u32);
-    TimerFront::run(settings);
+use timer_core::{Clock, Event, Lap, Mode, Sequence, SystemClock, TickState, Timer};
+
+use alarm::{AudioSink, Speaker};
+use cli::{Options, TimeFormat};
+use history::{Format, History, Session};
+
+mod alarm;
+mod cli;
+mod history;
+
+const FASTEST_COLOR: Color = Color::from_rgb(0.1, 0.6, 0.2);
+const SLOWEST_COLOR: Color = Color::from_rgb(0.8, 0.2, 0.2);
+const FINISHED_COLOR: Color = Color::from_rgb(0.8, 0.2, 0.2);
+
+fn main() -> iced::Result {
+    let options = Options::parse(env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{}\n\n{}", error, cli::USAGE);
+        process::exit(2);
+    });
+    if options.help {
+        print!("{}", cli::USAGE);
+        return Ok(());
+    }
+
+    let sequence = match &options.sequence {
+        Some(path) => Sequence::from_file(path).unwrap_or_else(|error| {
+            eprintln!("{}: {}", path.display(), error);
+            process::exit(1);
+        }),
+        None => Sequence::pomodoro(),
+    };
+
+    let history = match History::default_path() {
+        Some(path) => match History::load(path.clone()) {
+            Ok((history, skipped)) => {
+                for error in skipped {
+                    eprintln!("{}: {}", path.display(), error);
+                }
+                history
+            }
+            Err(error) => {
+                eprintln!("{}: {}", path.display(), error);
+                History::new(Some(path))
+            }
+        },
+        None => History::default(),
+    };
+
+    let mut settings = Settings::with_flags(Flags {
+        alarm: Box::new(Speaker),
+        clock: Box::new(SystemClock),
+        sequence,
+        history,
+        options: options.clone(),
+    });
+    settings.window.size = options.window_size;
+    settings.window.always_on_top = options.always_on_top;
+    TimerFront::run(settings)
 }
 
 struct TimerFront {
     start_stop_button_state: button::State,
     reset_button_state: button::State,
-    tick_state: TickState,
+    lap_button_state: button::State,
+    skip_button_state: button::State,
+    restart_button_state: button::State,
+    history_button_state: button::State,
+    export_csv_button_state: button::State,
+    export_json_button_state: button::State,
+    laps_scroll: scrollable::State,
+    history_scroll: scrollable::State,
+    label_input: text_input::State,
+    countdown_inputs: [CountdownInput; 3],
+    timer: Timer<Box<dyn Clock>>,
+    alarm: Box<dyn AudioSink>,
+    /// Whether the finished display is lit, as it flashes.
+    flash: bool,
+    /// When the current run was first started, until it is logged.
+    started: Option<DateTime<Local>>,
+    label: String,
+    history: History,
+    showing_history: bool,
+    /// The outcome of the last export.
+    export_status: Option<String>,
+    format: TimeFormat,
 }
 
 #[derive(Debug, Clone)]
@@ -20,26 +101,86 @@ enum Message {
     Start,
     Stop,
     Reset,
+    Lap,
+    Tick,
+    ModeSelected(Mode),
+    CountdownChanged(usize, String),
+    Flash,
+    SkipPhase,
+    RestartPhase,
+    LabelChanged(String),
+    HistoryToggled,
+    Export(Format),
+}
+
+struct Flags {
+    alarm: Box<dyn AudioSink>,
+    clock: Box<dyn Clock>,
+    sequence: Sequence,
+    history: History,
+    options: Options,
 }
 
 impl Application for TimerFront {
     type Executor = executor::Default;
     type Message = Message;
-    type Flags = ();
+    type Flags = Flags;
 
-    fn new(_flags: ()) -> (Self, Command<Self::Message>) {
-        (
-            Self {
-                start_stop_button_state: button::State::new(),
-                reset_button_state: button::State::new(),
-                tick_state: TickState::Stopped,
-            },
-            Command::none(),
-        )
+    fn new(flags: Flags) -> (Self, Command<Self::Message>) {
+        let options = flags.options;
+        let mode = if options.countdown.is_some() {
+            Mode::Countdown
+        } else if options.sequence.is_some() {
+            Mode::Sequence
+        } else {
+            Mode::Stopwatch
+        };
+
+        let mut front = Self {
+            start_stop_button_state: button::State::new(),
+            reset_button_state: button::State::new(),
+            lap_button_state: button::State::new(),
+            skip_button_state: button::State::new(),
+            restart_button_state: button::State::new(),
+            history_button_state: button::State::new(),
+            export_csv_button_state: button::State::new(),
+            export_json_button_state: button::State::new(),
+            laps_scroll: scrollable::State::new(),
+            history_scroll: scrollable::State::new(),
+            label_input: text_input::State::new(),
+            countdown_inputs: Default::default(),
+            timer: Timer::new(flags.clock, flags.sequence),
+            alarm: flags.alarm,
+            flash: false,
+            started: None,
+            label: options.label.unwrap_or_default(),
+            history: flags.history,
+            showing_history: false,
+            export_status: None,
+            format: options.format,
+        };
+
+        front.timer.set_mode(mode);
+        if let Some(countdown) = options.countdown {
+            let seconds = countdown.as_secs();
+            let values = [seconds / 3600, seconds / 60 % 60, seconds % 60];
+            for (input, value) in front.countdown_inputs.iter_mut().zip(&values) {
+                input.value = format!("{:02}", value);
+            }
+            front.timer.set_countdown(Some(countdown));
+        }
+        if options.start {
+            front.start();
+        }
+
+        (front, Command::none())
     }
 
     fn title(&self) -> String {
-        String::from("Timer")
+        match self.timer.mode() {
+            Mode::Sequence => format!("Timer - {}", self.timer.phase().label),
+            _ => String::from("Timer"),
+        }
     }
 
     fn update(
@@ -49,38 +190,157 @@ impl Application for TimerFront {
     ) -> Command<Self::Message> {
         match message {
             Message::Start => {
-                self.tick_state = TickState::Ticking;
+                self.start();
             }
             Message::Stop => {
-                self.tick_state = TickState::Stopped;
+                let event = self.timer.stop();
+                self.handle(event);
+            }
+            Message::Reset => {
+                // A stopwatch only ever ends when it is reset.
+                if self.timer.mode() == Mode::Stopwatch {
+                    self.log_session();
+                }
+                self.timer.reset();
+                self.started = if self.timer.is_ticking() {
+                    Some(Local::now())
+                } else {
+                    None
+                };
+            }
+            Message::Lap => {
+                self.timer.lap();
+            }
+            Message::Tick => {
+                let event = self.timer.tick();
+                self.handle(event);
+            }
+            Message::ModeSelected(mode) => {
+                if self.timer.mode() == Mode::Stopwatch {
+                    self.log_session();
+                }
+                self.started = None;
+                self.timer.set_mode(mode);
+            }
+            Message::CountdownChanged(index, value) => {
+                if value.chars().all(|c| c.is_ascii_digit()) && value.len() <= 2 {
+                    self.countdown_inputs[index].value = value;
+                    self.timer.set_countdown(Some(self.countdown()));
+                }
+            }
+            Message::Flash => {
+                self.flash = !self.flash;
+            }
+            Message::SkipPhase => {
+                self.timer.skip_phase();
+            }
+            Message::RestartPhase => {
+                self.timer.restart_phase();
+            }
+            Message::LabelChanged(label) => {
+                self.label = label;
+            }
+            Message::HistoryToggled => {
+                self.showing_history = !self.showing_history;
+                self.export_status = None;
+            }
+            Message::Export(format) => {
+                if let Some(path) = self.history.path() {
+                    let path = path.with_extension(format.extension());
+                    self.export_status = Some(match self.history.export(format, &path) {
+                        Ok(()) => format!("Exported to {}", path.display()),
+                        Err(error) => format!("Could not export: {}", error),
+                    });
+                }
             }
-            Message::Reset => {}
         }
         Command::none()
     }
 
+    fn subscription(&self) -> Subscription<Self::Message> {
+        match self.timer.tick_state() {
+            TickState::Stopped => Subscription::none(),
+            TickState::Ticking { .. } => {
+                time::every(Duration::from_millis(10)).map(|_| Message::Tick)
+            }
+            TickState::Finished => time::every(Duration::from_millis(500)).map(|_| Message::Flash),
+        }
+    }
+
     fn view(&mut self) -> Element<Self::Message> {
-        let duration_text = "00:00:00.00";
+        if self.showing_history {
+            return self.history_view();
+        }
+
+        let mode = self.timer.mode();
+        let tick_state = self.timer.tick_state();
+        let duration_text = self.format.format(
+            self.timer
+                .remaining()
+                .unwrap_or_else(|| self.timer.elapsed()),
+        );
 
-        let start_stop_text = match self.tick_state {
-            TickState::Stopped => {
+        let start_stop_text = match tick_state {
+            TickState::Stopped | TickState::Finished => {
                 Text::new("Start").horizontal_alignment(HorizontalAlignment::Center)
             }
-            TickState::Ticking => {
+            TickState::Ticking { .. } => {
                 Text::new("Stop").horizontal_alignment(HorizontalAlignment::Center)
             }
         };
 
-        let start_stop_message = match self.tick_state {
-            TickState::Stopped => Message::Start,
-            TickState::Ticking => Message::Stop,
+        let start_stop_message = match tick_state {
+            TickState::Ticking { .. } => Some(Message::Stop),
+            _ if self.timer.can_start() => Some(Message::Start),
+            _ => None,
         };
 
-        let tick_text = Text::new(duration_text).size(60);
+        let mut tick_text = Text::new(duration_text).size(60);
+        if let TickState::Finished = tick_state {
+            tick_text = tick_text.color(if self.flash {
+                FINISHED_COLOR
+            } else {
+                Color::BLACK
+            });
+        }
 
-        let start_stop_button = Button::new(&mut self.start_stop_button_state, start_stop_text)
-            .min_width(80)
-            .on_press(start_stop_message);
+        let modes = [Mode::Stopwatch, Mode::Countdown, Mode::Sequence]
+            .iter()
+            .fold(Row::new().spacing(20), |modes, choice| {
+                modes.push(Radio::new(
+                    *choice,
+                    choice.name(),
+                    Some(mode),
+                    Message::ModeSelected,
+                ))
+            });
+
+        let label = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(
+                TextInput::new(
+                    &mut self.label_input,
+                    "Label (optional)",
+                    &self.label,
+                    Message::LabelChanged,
+                )
+                .padding(5),
+            )
+            .push(
+                Button::new(
+                    &mut self.history_button_state,
+                    Text::new("History").horizontal_alignment(HorizontalAlignment::Center),
+                )
+                .min_width(80)
+                .on_press(Message::HistoryToggled),
+            );
+
+        let mut start_stop_button =
+            Button::new(&mut self.start_stop_button_state, start_stop_text).min_width(80);
+        if let Some(message) = start_stop_message {
+            start_stop_button = start_stop_button.on_press(message);
+        }
 
         let reset_button = Button::new(
             &mut self.reset_button_state,
@@ -89,13 +349,249 @@ impl Application for TimerFront {
         .min_width(80)
         .on_press(Message::Reset);
 
-        Column::new()
-            .push(tick_text)
+        let mut controls = Row::new().push(start_stop_button).spacing(10);
+
+        if mode == Mode::Stopwatch {
+            let mut lap_button = Button::new(
+                &mut self.lap_button_state,
+                Text::new("Lap").horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .min_width(80);
+            if self.timer.is_ticking() {
+                lap_button = lap_button.on_press(Message::Lap);
+            }
+            controls = controls.push(lap_button);
+        }
+        if mode == Mode::Sequence {
+            let mut skip_button = Button::new(
+                &mut self.skip_button_state,
+                Text::new("Skip").horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .min_width(80);
+            if self.timer.phase_index() + 1 < self.timer.sequence().phases.len() {
+                skip_button = skip_button.on_press(Message::SkipPhase);
+            }
+
+            let restart_button = Button::new(
+                &mut self.restart_button_state,
+                Text::new("Restart").horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .min_width(80)
+            .on_press(Message::RestartPhase);
+
+            controls = controls.push(skip_button).push(restart_button);
+        }
+        controls = controls.push(reset_button);
+
+        let mut content = Column::new().push(modes).push(label);
+        if mode == Mode::Sequence {
+            content = content.push(Text::new(&self.timer.phase().label).size(30));
+        }
+        let content = content.push(tick_text);
+
+        // The countdown can only be set before it starts.
+        let editing = mode == Mode::Countdown
+            && tick_state == TickState::Stopped
+            && self.timer.elapsed() == Duration::default();
+
+        let content = if editing {
+            let inputs = self.countdown_inputs.iter_mut().enumerate().fold(
+                Row::new().spacing(5).align_items(Align::Center),
+                |inputs, (index, input)| {
+                    let inputs = if index > 0 {
+                        inputs.push(Text::new(":"))
+                    } else {
+                        inputs
+                    };
+
+                    inputs.push(
+                        TextInput::new(
+                            &mut input.state,
+                            ["hh", "mm", "ss"][index],
+                            &input.value,
+                            move |value| Message::CountdownChanged(index, value),
+                        )
+                        .padding(5)
+                        .width(Length::Units(50)),
+                    )
+                },
+            );
+
+            content.push(inputs).push(controls)
+        } else if mode == Mode::Countdown {
+            content.push(controls)
+        } else if mode == Mode::Sequence {
+            let sequence = self.timer.sequence();
+            let total = sequence.total();
+            let done = self.timer.run_time();
+
+            content
+                .push(controls)
+                .push(Text::new(format!(
+                    "{}: phase {} of {}",
+                    sequence.name,
+                    self.timer.phase_index() + 1,
+                    sequence.phases.len()
+                )))
+                .push(ProgressBar::new(
+                    0.0..=total.as_secs_f32(),
+                    done.as_secs_f32(),
+                ))
+                .push(Text::new(format!(
+                    "{} of {}",
+                    self.format.format(done),
+                    self.format.format(total)
+                )))
+        } else {
+            content.push(controls).push(lap_list(
+                self.timer.laps(),
+                self.format,
+                Scrollable::new(&mut self.laps_scroll),
+            ))
+        };
+
+        content
+            .spacing(10)
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_items(Align::Center)
+            .into()
+    }
+}
+
+impl TimerFront {
+    fn start(&mut self) {
+        if !self.timer.can_start() {
+            return;
+        }
+        if self.started.is_none() {
+            self.started = Some(Local::now());
+        }
+        self.timer.start();
+    }
+
+    /// Sounds the alarm once a countdown or phase runs out, and logs the run
+    /// once it is over.
+    fn handle(&mut self, event: Option<Event>) {
+        match event {
+            Some(Event::PhaseEnded) => self.alarm.play(),
+            Some(Event::Finished) => {
+                self.alarm.play();
+                self.flash = true;
+                self.log_session();
+            }
+            None => {}
+        }
+    }
+
+    /// Records the run that just ended in the history, unless it never got
+    /// going.
+    fn log_session(&mut self) {
+        let started = match self.started.take() {
+            Some(started) => started,
+            None => return,
+        };
+        let duration = self.timer.run_time();
+        if duration == Duration::default() {
+            return;
+        }
+
+        let label = self.label.trim();
+        let session = Session {
+            started,
+            mode: self.timer.mode().name().to_string(),
+            duration,
+            laps: self.timer.laps().iter().map(|lap| lap.split).collect(),
+            label: Some(label.to_string()).filter(|_| !label.is_empty()),
+        };
+        if let Err(error) = self.history.record(session) {
+            eprintln!("could not log the session: {}", error);
+        }
+    }
+
+    fn history_view(&mut self) -> Element<Message> {
+        let format = self.format;
+        let days = self.history.days().iter().fold(
+            Column::new().spacing(5).push(Text::new("Per day").size(24)),
+            |days, day| {
+                days.push(
+                    Row::new()
+                        .push(Text::new(day.date.to_string()).width(Length::Fill))
+                        .push(Text::new(format!("{} sessions", day.sessions)).width(Length::Fill))
+                        .push(Text::new(format.format(day.total)).width(Length::Fill)),
+                )
+            },
+        );
+
+        let sessions = self.history.sessions().iter().rev().fold(
+            Column::new()
+                .spacing(5)
+                .push(Text::new("Sessions").size(24)),
+            |sessions, session| {
+                sessions.push(
+                    Row::new()
+                        .push(
+                            Text::new(session.started.format("%Y-%m-%d %H:%M").to_string())
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            Text::new(session.label.as_deref().unwrap_or(&session.mode))
+                                .width(Length::Fill),
+                        )
+                        .push(Text::new(format.format(session.duration)).width(Length::Fill)),
+                )
+            },
+        );
+
+        let exportable = self.history.path().is_some() && !self.history.sessions().is_empty();
+        let export_button = |state, label, format| {
+            let button = Button::new(
+                state,
+                Text::new(label).horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .min_width(80);
+            if exportable {
+                button.on_press(Message::Export(format))
+            } else {
+                button
+            }
+        };
+
+        let controls = Row::new()
+            .spacing(10)
+            .push(export_button(
+                &mut self.export_csv_button_state,
+                "Export CSV",
+                Format::Csv,
+            ))
+            .push(export_button(
+                &mut self.export_json_button_state,
+                "Export JSON",
+                Format::Json,
+            ))
             .push(
-                Row::new()
-                    .push(start_stop_button)
-                    .push(reset_button)
-                    .spacing(10),
+                Button::new(
+                    &mut self.history_button_state,
+                    Text::new("Back").horizontal_alignment(HorizontalAlignment::Center),
+                )
+                .min_width(80)
+                .on_press(Message::HistoryToggled),
+            );
+
+        let mut content = Column::new().push(controls);
+        if let Some(status) = &self.export_status {
+            content = content.push(Text::new(status.as_str()).size(16));
+        }
+
+        content
+            .push(
+                Scrollable::new(&mut self.history_scroll)
+                    .spacing(20)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .push(days)
+                    .push(sessions),
             )
             .spacing(10)
             .padding(10)
@@ -104,9 +600,152 @@ impl Application for TimerFront {
             .align_items(Align::Center)
             .into()
     }
+
+    /// The duration typed into the countdown inputs.
+    fn countdown(&self) -> Duration {
+        let [hours, minutes, seconds] = &self.countdown_inputs;
+        Duration::from_secs(hours.number() * 3600 + minutes.number() * 60 + seconds.number())
+    }
+}
+
+fn lap_list<'a>(
+    laps: &[Lap],
+    format: TimeFormat,
+    scrollable: Scrollable<'a, Message>,
+) -> Scrollable<'a, Message> {
+    // Only worth pointing out once there is something to compare.
+    let (fastest, slowest) = if laps.len() > 1 {
+        (
+            (0..laps.len()).min_by_key(|index| laps[*index].split),
+            (0..laps.len()).max_by_key(|index| laps[*index].split),
+        )
+    } else {
+        (None, None)
+    };
+
+    laps.iter().enumerate().rev().fold(
+        scrollable
+            .spacing(5)
+            .width(Length::Fill)
+            .height(Length::Fill),
+        |list, (index, lap)| {
+            let color = if Some(index) == fastest {
+                FASTEST_COLOR
+            } else if Some(index) == slowest {
+                SLOWEST_COLOR
+            } else {
+                Color::BLACK
+            };
+
+            list.push(
+                Row::new()
+                    .push(Text::new(format!("Lap {}", index + 1)).width(Length::Fill))
+                    .push(
+                        Text::new(format.format(lap.split))
+                            .color(color)
+                            .width(Length::Fill),
+                    )
+                    .push(Text::new(format.format(lap.total)).width(Length::Fill)),
+            )
+        },
+    )
+}
+
+#[derive(Default)]
+struct CountdownInput {
+    value: String,
+    state: text_input::State,
+}
+
+impl CountdownInput {
+    fn number(&self) -> u64 {
+        self.value.parse().unwrap_or(0)
+    }
+}
+
+/// Formats `duration` as hours, minutes, seconds and centiseconds, e.g.
+/// "01:02:03.04".
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    format!(
+        "{:02}:{:02}:{:02}.{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        duration.subsec_millis() / 10
+    )
 }
 
-enum TickState {
-    Stopped,
-    Ticking,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use timer_core::ManualClock;
+
+    use crate::alarm::Silent;
+
+    fn front(silent: &Silent, clock: &ManualClock) -> TimerFront {
+        let (front, _) = TimerFront::new(Flags {
+            alarm: Box::new(silent.clone()),
+            clock: Box::new(clock.clone()),
+            sequence: Sequence::pomodoro(),
+            history: History::default(),
+            options: Options::default(),
+        });
+        front
+    }
+
+    #[test]
+    fn test_countdown_finishes_once() {
+        let silent = Silent::default();
+        let clock = ManualClock::new();
+        let mut front = front(&silent, &clock);
+
+        front.timer.set_mode(Mode::Countdown);
+        front.countdown_inputs[2].value = "5".to_string();
+        front.timer.set_countdown(Some(front.countdown()));
+        front.start();
+
+        clock.advance(Duration::from_secs(3));
+        let event = front.timer.tick();
+        front.handle(event);
+        assert_eq!(silent.plays(), 0);
+
+        for _ in 0..2 {
+            clock.advance(Duration::from_secs(3));
+            let event = front.timer.tick();
+            front.handle(event);
+        }
+        assert_eq!(front.timer.tick_state(), TickState::Finished);
+        assert_eq!(silent.plays(), 1);
+        assert_eq!(front.history.sessions().len(), 1);
+        assert_eq!(front.history.sessions()[0].duration, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_sequence_sounds_every_phase() {
+        let silent = Silent::default();
+        let clock = ManualClock::new();
+        let mut front = front(&silent, &clock);
+
+        front.timer.set_mode(Mode::Sequence);
+        front.start();
+
+        clock.advance(Duration::from_secs(26 * 60));
+        let event = front.timer.tick();
+        front.handle(event);
+        assert_eq!(front.timer.phase_index(), 1);
+        assert_eq!(silent.plays(), 1);
+        assert!(front.history.sessions().is_empty());
+
+        clock.advance(front.timer.sequence().total());
+        for _ in 1..front.timer.sequence().phases.len() {
+            let event = front.timer.tick();
+            front.handle(event);
+        }
+        assert_eq!(front.timer.tick_state(), TickState::Finished);
+        assert_eq!(silent.plays(), 8);
+        assert_eq!(front.history.sessions().len(), 1);
+    }
 }
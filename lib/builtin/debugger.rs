@@ -0,0 +1,43 @@
+use iced::{Checkbox, Column, Element, Text};
+
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+pub struct DebuggerStep;
+
+impl Step for DebuggerStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "debugger.title"
+    }
+
+    fn update(&mut self, message: StepMessage, debug: &mut bool) {
+        if let StepMessage::DebugToggled(value) = message {
+            *debug = value;
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(catalog.text("debugger.description")))
+            .push(Text::new(catalog.text("debugger.instructions")))
+            .push(
+                Checkbox::new(
+                    context.debug,
+                    catalog.text("debugger.checkbox"),
+                    StepMessage::DebugToggled,
+                )
+                .style(context.theme),
+            )
+            .push(Text::new(catalog.text("debugger.footer")))
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,30 @@
+use iced::{Column, Element, Text};
+
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+pub struct EndStep;
+
+impl Step for EndStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "end.title"
+    }
+
+    fn update(&mut self, _message: StepMessage, _debug: &mut bool) {}
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(catalog.text("end.description")))
+            .push(Text::new(catalog.text("end.footer")))
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        false
+    }
+}
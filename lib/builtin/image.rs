@@ -0,0 +1,72 @@
+use iced::{slider, Column, Container, Element, HorizontalAlignment, Image, Length, Slider, Text};
+
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+pub struct ImageStep {
+    width: u16,
+    slider: slider::State,
+}
+
+impl Default for ImageStep {
+    fn default() -> Self {
+        Self {
+            width: 300,
+            slider: slider::State::new(),
+        }
+    }
+}
+
+impl Step for ImageStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "image.title"
+    }
+
+    fn update(&mut self, message: StepMessage, _debug: &mut bool) {
+        if let StepMessage::ImageWidthChanged(width) = message {
+            self.width = width;
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(catalog.text("image.description")))
+            .push(ice_cube(self.width))
+            .push(
+                Slider::new(
+                    &mut self.slider,
+                    100..=500,
+                    self.width,
+                    StepMessage::ImageWidthChanged,
+                )
+                .style(context.theme),
+            )
+            .push(
+                Text::new(catalog.format("image.width", &[("width", &self.width.to_string())]))
+                    .width(Length::Fill)
+                    .horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
+
+fn ice_cube<'a>(width: u16) -> Container<'a, StepMessage> {
+    Container::new(
+        Image::new(format!(
+            "{}/images/ice-cube.png",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .width(Length::Units(width)),
+    )
+    .width(Length::Fill)
+    .center_x()
+}
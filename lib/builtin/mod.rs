@@ -0,0 +1,37 @@
+pub mod debugger;
+pub mod end;
+pub mod image;
+pub mod page;
+pub mod radio;
+pub mod rows_and_columns;
+pub mod slider;
+pub mod text;
+pub mod text_input;
+
+pub use debugger::DebuggerStep;
+pub use end::EndStep;
+pub use image::ImageStep;
+pub use page::PageStep;
+pub use radio::RadioStep;
+pub use rows_and_columns::RowsAndColumnsStep;
+pub use slider::SliderStep;
+pub use text::TextStep;
+pub use text_input::TextInputStep;
+
+use crate::definition::StepKind;
+use crate::step::Step;
+use crate::step_message::StepMessage;
+
+pub fn new(kind: StepKind) -> Box<dyn Step<Message = StepMessage>> {
+    match kind {
+        StepKind::Page => Box::new(PageStep::new(String::new(), Vec::new())),
+        StepKind::Slider => Box::new(SliderStep::default()),
+        StepKind::RowsAndColumns => Box::new(RowsAndColumnsStep::default()),
+        StepKind::Text => Box::new(TextStep::default()),
+        StepKind::Radio => Box::new(RadioStep::default()),
+        StepKind::Image => Box::new(ImageStep::default()),
+        StepKind::TextInput => Box::new(TextInputStep::default()),
+        StepKind::Debugger => Box::new(DebuggerStep),
+        StepKind::End => Box::new(EndStep),
+    }
+}
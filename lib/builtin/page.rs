@@ -0,0 +1,40 @@
+use iced::{Column, Element, Text};
+
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+/// A page of plain paragraphs, e.g. a welcome screen. The title and
+/// paragraphs may be catalog keys.
+pub struct PageStep {
+    title: String,
+    body: Vec<String>,
+}
+
+impl PageStep {
+    pub fn new(title: String, body: Vec<String>) -> PageStep {
+        PageStep { title, body }
+    }
+}
+
+impl Step for PageStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn update(&mut self, _message: StepMessage, _debug: &mut bool) {}
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        self.body
+            .iter()
+            .fold(Column::new().spacing(20), |page, paragraph| {
+                page.push(Text::new(context.catalog.text(paragraph)))
+            })
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
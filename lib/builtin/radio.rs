@@ -0,0 +1,70 @@
+use iced::{Column, Element, Radio, Text};
+
+use crate::language::Language;
+use crate::step::{Answer, Context, Step};
+use crate::step_message::StepMessage;
+
+#[derive(Default)]
+pub struct RadioStep {
+    selection: Option<Language>,
+}
+
+impl Step for RadioStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "radio.title"
+    }
+
+    fn update(&mut self, message: StepMessage, _debug: &mut bool) {
+        if let StepMessage::LanguageSelected(language) = message {
+            self.selection = Some(language);
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+        let selection = self.selection;
+
+        let question = Column::new()
+            .padding(20)
+            .spacing(10)
+            .push(Text::new(catalog.text("radio.question")).size(24))
+            .push(Language::all().iter().cloned().fold(
+                Column::new().padding(10).spacing(20),
+                |choices, language| {
+                    choices.push(
+                        Radio::new(
+                            language,
+                            catalog.text(language.key()),
+                            selection,
+                            StepMessage::LanguageSelected,
+                        )
+                        .style(context.theme),
+                    )
+                },
+            ));
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(catalog.text("radio.description")))
+            .push(question)
+            .push(Text::new(catalog.text("radio.footer")))
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        self.selection == Some(Language::Rust)
+    }
+
+    fn answer(&self) -> Option<Answer> {
+        self.selection.map(Answer::Language)
+    }
+
+    fn restore(&mut self, answer: Option<Answer>) {
+        self.selection = match answer {
+            Some(Answer::Language(language)) => Some(language),
+            _ => None,
+        };
+    }
+}
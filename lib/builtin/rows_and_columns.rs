@@ -0,0 +1,108 @@
+use iced::{slider, Column, Element, HorizontalAlignment, Length, Radio, Row, Slider, Text};
+
+use crate::layout::Layout;
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+pub struct RowsAndColumnsStep {
+    layout: Layout,
+    spacing_slider: slider::State,
+    spacing: u16,
+}
+
+impl Default for RowsAndColumnsStep {
+    fn default() -> Self {
+        Self {
+            layout: Layout::Row,
+            spacing_slider: slider::State::new(),
+            spacing: 20,
+        }
+    }
+}
+
+impl Step for RowsAndColumnsStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "rows_and_columns.title"
+    }
+
+    fn update(&mut self, message: StepMessage, _debug: &mut bool) {
+        match message {
+            StepMessage::LayoutChanged(layout) => self.layout = layout,
+            StepMessage::SpacingChanged(spacing) => self.spacing = spacing,
+            _ => {}
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+        let Self {
+            layout,
+            spacing_slider,
+            spacing,
+        } = self;
+
+        let row_radio = Radio::new(
+            Layout::Row,
+            catalog.text("rows_and_columns.row"),
+            Some(*layout),
+            StepMessage::LayoutChanged,
+        )
+        .style(context.theme);
+
+        let column_radio = Radio::new(
+            Layout::Column,
+            catalog.text("rows_and_columns.column"),
+            Some(*layout),
+            StepMessage::LayoutChanged,
+        )
+        .style(context.theme);
+
+        let layout_section: Element<_> = match layout {
+            Layout::Row => Row::new()
+                .spacing(*spacing)
+                .push(row_radio)
+                .push(column_radio)
+                .into(),
+            Layout::Column => Column::new()
+                .spacing(*spacing)
+                .push(row_radio)
+                .push(column_radio)
+                .into(),
+        };
+
+        let spacing_section = Column::new()
+            .spacing(10)
+            .push(
+                Slider::new(
+                    spacing_slider,
+                    0..=80,
+                    *spacing,
+                    StepMessage::SpacingChanged,
+                )
+                .style(context.theme),
+            )
+            .push(
+                Text::new(catalog.format(
+                    "rows_and_columns.spacing",
+                    &[("spacing", &spacing.to_string())],
+                ))
+                .width(Length::Fill)
+                .horizontal_alignment(HorizontalAlignment::Center),
+            );
+
+        Column::new()
+            .spacing(*spacing)
+            .push(Text::new(catalog.text("rows_and_columns.description")))
+            .push(Text::new(catalog.text("rows_and_columns.explanation")))
+            .push(layout_section)
+            .push(Text::new(catalog.text("rows_and_columns.spacing_hint")))
+            .push(spacing_section)
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
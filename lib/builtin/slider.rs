@@ -0,0 +1,60 @@
+use iced::{slider, Column, Element, HorizontalAlignment, Length, Slider, Text};
+
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+pub struct SliderStep {
+    state: slider::State,
+    value: u8,
+}
+
+impl Default for SliderStep {
+    fn default() -> Self {
+        Self {
+            state: slider::State::new(),
+            value: 50,
+        }
+    }
+}
+
+impl Step for SliderStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "slider.title"
+    }
+
+    fn update(&mut self, message: StepMessage, _debug: &mut bool) {
+        if let StepMessage::SliderChanged(value) = message {
+            self.value = value;
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(catalog.text("slider.description")))
+            .push(Text::new(catalog.text("slider.instructions")))
+            .push(
+                Slider::new(
+                    &mut self.state,
+                    0..=100,
+                    self.value,
+                    StepMessage::SliderChanged,
+                )
+                .style(context.theme),
+            )
+            .push(
+                Text::new(self.value.to_string())
+                    .width(Length::Fill)
+                    .horizontal_alignment(HorizontalAlignment::Center),
+            )
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
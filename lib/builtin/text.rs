@@ -0,0 +1,114 @@
+use iced::{slider, Color, Column, Element, Row, Slider, Text};
+use widgets::Theme;
+
+use crate::step::{Context, Step};
+use crate::step_message::StepMessage;
+
+pub struct TextStep {
+    size_slider: slider::State,
+    size: u16,
+    color_sliders: [slider::State; 3],
+    /// `None` until a slider moves, following the theme's text color.
+    color: Option<Color>,
+}
+
+impl Default for TextStep {
+    fn default() -> Self {
+        Self {
+            size_slider: slider::State::new(),
+            size: 30,
+            color_sliders: [
+                slider::State::new(),
+                slider::State::new(),
+                slider::State::new(),
+            ],
+            color: None,
+        }
+    }
+}
+
+impl Step for TextStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "text.title"
+    }
+
+    fn update(&mut self, message: StepMessage, _debug: &mut bool) {
+        match message {
+            StepMessage::TextSizeChanged(size) => self.size = size,
+            StepMessage::TextColorChanged(color) => self.color = Some(color),
+            _ => {}
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+        let Self {
+            size_slider,
+            size,
+            color_sliders,
+            color,
+        } = self;
+        let (size, color) = (*size, color.unwrap_or(context.theme.palette().text));
+
+        let size_section = Column::new()
+            .padding(20)
+            .spacing(20)
+            .push(Text::new(catalog.text("text.size_hint")))
+            .push(Text::new(catalog.format("text.size", &[("size", &size.to_string())])).size(size))
+            .push(
+                Slider::new(size_slider, 10..=70, size, StepMessage::TextSizeChanged)
+                    .style(context.theme),
+            );
+
+        let [red, green, blue] = color_sliders;
+        let theme = context.theme;
+
+        let color_sliders = Row::new()
+            .spacing(10)
+            .push(color_slider(red, theme, color.r, move |r| Color {
+                r,
+                ..color
+            }))
+            .push(color_slider(green, theme, color.g, move |g| Color {
+                g,
+                ..color
+            }))
+            .push(color_slider(blue, theme, color.b, move |b| Color {
+                b,
+                ..color
+            }));
+
+        let color_section = Column::new()
+            .padding(20)
+            .spacing(20)
+            .push(Text::new(catalog.text("text.color_hint")))
+            .push(Text::new(format!("{:?}", color)).color(color))
+            .push(color_sliders);
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(catalog.text("text.description")))
+            .push(size_section)
+            .push(color_section)
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
+
+fn color_slider(
+    state: &mut slider::State,
+    theme: Theme,
+    component: f32,
+    update: impl Fn(f32) -> Color + 'static,
+) -> Slider<f64, StepMessage> {
+    Slider::new(state, 0.0..=1.0, f64::from(component), move |c| {
+        StepMessage::TextColorChanged(update(c as f32))
+    })
+    .step(0.01)
+    .style(theme)
+}
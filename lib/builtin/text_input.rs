@@ -0,0 +1,150 @@
+use iced::{text_input, Checkbox, Column, Element, Length, ProgressBar, Text, TextInput};
+
+use crate::password::Strength;
+use crate::step::{Answer, Context, Step};
+use crate::step_message::StepMessage;
+use crate::validator::Validator;
+
+#[derive(Default)]
+pub struct TextInputStep {
+    value: String,
+    is_secure: bool,
+    state: text_input::State,
+    validators: Vec<Validator>,
+}
+
+impl TextInputStep {
+    pub fn with_validators(mut self, validators: Vec<Validator>) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    fn failed(&self) -> impl Iterator<Item = &Validator> {
+        let value = self.value.as_str();
+        self.validators
+            .iter()
+            .filter(move |validator| !validator.check(value))
+    }
+}
+
+impl Step for TextInputStep {
+    type Message = StepMessage;
+
+    fn title(&self) -> &str {
+        "text_input.title"
+    }
+
+    fn update(&mut self, message: StepMessage, _debug: &mut bool) {
+        match message {
+            StepMessage::InputChanged(value) => self.value = value,
+            StepMessage::ToggleSecureInput(is_secure) => self.is_secure = is_secure,
+            _ => {}
+        }
+    }
+
+    fn view(&mut self, context: &Context) -> Element<StepMessage> {
+        let catalog = &context.catalog;
+        let palette = context.theme.palette();
+
+        // Complaining about an input the user has not started on yet would
+        // only be noise.
+        let errors = if self.value.is_empty() {
+            Vec::new()
+        } else {
+            self.failed()
+                .map(|validator| validator.error(catalog))
+                .collect()
+        };
+
+        let mut text_input = TextInput::new(
+            &mut self.state,
+            catalog.text("text_input.placeholder"),
+            &self.value,
+            StepMessage::InputChanged,
+        )
+        .on_submit(StepMessage::InputSubmitted)
+        .padding(10)
+        .size(30)
+        .style(context.theme);
+        if self.is_secure {
+            text_input = text_input.password();
+        }
+
+        let input = errors
+            .into_iter()
+            .fold(Column::new().spacing(5).push(text_input), |input, error| {
+                input.push(Text::new(error).size(16).color(palette.error))
+            });
+
+        let secure = Checkbox::new(
+            self.is_secure,
+            catalog.text("text_input.secure"),
+            StepMessage::ToggleSecureInput,
+        )
+        .style(context.theme);
+
+        let feedback: Element<_> = if self.is_secure {
+            let strength = Strength::of(&self.value);
+
+            Column::new()
+                .spacing(5)
+                .push(Text::new(catalog.format(
+                    "password.strength",
+                    &[("strength", catalog.text(strength.key()))],
+                )))
+                .push(
+                    ProgressBar::new(
+                        Strength::Weak.level()..=Strength::Strong.level(),
+                        strength.level(),
+                    )
+                    .height(Length::Units(8))
+                    .style(context.theme),
+                )
+                .into()
+        } else {
+            Text::new(self.value.as_str()).into()
+        };
+
+        Column::new()
+            .spacing(20)
+            .push(input)
+            .push(secure)
+            .push(feedback)
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        !self.value.is_empty() && self.failed().next().is_none()
+    }
+
+    fn focus(&mut self, focused: bool) {
+        if focused {
+            self.state.focus();
+        } else {
+            self.state.unfocus();
+        }
+    }
+
+    fn is_submit(&self, message: &StepMessage) -> bool {
+        matches!(message, StepMessage::InputSubmitted)
+    }
+
+    /// An input that fails validation is no answer.
+    fn answer(&self) -> Option<Answer> {
+        if self.failed().next().is_some() {
+            return None;
+        }
+        Some(Answer::Text(self.value.clone()))
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.is_secure
+    }
+
+    fn restore(&mut self, answer: Option<Answer>) {
+        self.value = match answer {
+            Some(Answer::Text(value)) => value,
+            _ => String::new(),
+        };
+    }
+}
@@ -0,0 +1,144 @@
+use iced::{Column, Element, Text};
+
+use crate::definition::Condition;
+use crate::step::{Answer, Context, Step, Successor};
+
+/// Overrides the title, extra paragraphs, continue condition and successor
+/// of another step, as a tour definition asks for.
+pub struct Configured<Message> {
+    id: Option<String>,
+    title: Option<String>,
+    body: Vec<String>,
+    condition: Option<Condition>,
+    branches: Vec<(Option<Condition>, String)>,
+    step: Box<dyn Step<Message = Message>>,
+}
+
+impl<Message> Configured<Message> {
+    pub fn new(step: Box<dyn Step<Message = Message>>) -> Self {
+        Self {
+            id: None,
+            title: None,
+            body: Vec::new(),
+            condition: None,
+            branches: Vec::new(),
+            step,
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_body(mut self, body: Vec<String>) -> Self {
+        self.body = body;
+        self
+    }
+
+    pub fn continue_when(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Jumps to the step `goto` when `when` holds, or always if it is `None`.
+    /// The first matching branch wins.
+    pub fn branch(mut self, when: Option<Condition>, goto: impl Into<String>) -> Self {
+        self.branches.push((when, goto.into()));
+        self
+    }
+}
+
+impl<Message: 'static> Step for Configured<Message> {
+    type Message = Message;
+
+    fn title(&self) -> &str {
+        match &self.title {
+            Some(title) => title,
+            None => self.step.title(),
+        }
+    }
+
+    fn update(&mut self, message: Message, debug: &mut bool) {
+        self.step.update(message, debug);
+    }
+
+    fn view(&mut self, context: &Context) -> Element<Message> {
+        self.body
+            .iter()
+            .fold(
+                Column::new().spacing(20).push(self.step.view(context)),
+                |content, paragraph| content.push(Text::new(context.catalog.text(paragraph))),
+            )
+            .into()
+    }
+
+    fn can_continue(&self) -> bool {
+        match &self.condition {
+            Some(condition) => condition.is_met(self.step.answer()),
+            None => self.step.can_continue(),
+        }
+    }
+
+    fn focus(&mut self, focused: bool) {
+        self.step.focus(focused);
+    }
+
+    fn is_submit(&self, message: &Message) -> bool {
+        self.step.is_submit(message)
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.id.as_deref().or_else(|| self.step.id())
+    }
+
+    fn successor(&self) -> Successor {
+        let answer = self.step.answer();
+
+        self.branches
+            .iter()
+            .find(|(when, _)| match when {
+                Some(condition) => condition.is_met(answer.clone()),
+                None => true,
+            })
+            .map(|(_, goto)| Successor::Goto(goto.clone()))
+            .unwrap_or_else(|| self.step.successor())
+    }
+
+    fn answer(&self) -> Option<Answer> {
+        self.step.answer()
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.step.is_sensitive()
+    }
+
+    fn restore(&mut self, answer: Option<Answer>) {
+        self.step.restore(answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::builtin::TextInputStep;
+    use crate::step_message::StepMessage;
+
+    #[test]
+    fn test_secure_input_can_continue() {
+        let mut step = Configured::new(Box::new(TextInputStep::default()))
+            .continue_when(Condition::InputNonEmpty);
+        step.update(StepMessage::InputChanged("hunter2".to_string()), &mut false);
+        assert!(step.can_continue());
+
+        step.update(StepMessage::ToggleSecureInput(true), &mut false);
+        assert!(step.can_continue());
+        assert!(step.is_sensitive());
+    }
+}
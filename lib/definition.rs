@@ -0,0 +1,477 @@
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::builtin::{self, PageStep, TextInputStep};
+use crate::configured::Configured;
+use crate::language::Language;
+use crate::step::{Answer, Step};
+use crate::step_message::StepMessage;
+use crate::validator::Validator;
+
+/// A tour described in a TOML or JSON file instead of in code.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TourDefinition {
+    pub steps: Vec<StepDefinition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StepDefinition {
+    pub id: Option<String>,
+    pub kind: StepKind,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub body: Vec<String>,
+    pub continue_when: Option<Condition>,
+    /// Rules the input of a `text_input` step must follow.
+    #[serde(default)]
+    pub validate: Vec<Validator>,
+    /// Where to go next; the first branch whose condition holds wins, and
+    /// the following step is the fallback.
+    #[serde(default)]
+    pub next: Vec<Branch>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Branch {
+    pub when: Option<Condition>,
+    pub goto: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepKind {
+    Page,
+    Slider,
+    RowsAndColumns,
+    Text,
+    Radio,
+    Image,
+    TextInput,
+    Debugger,
+    End,
+}
+
+/// When the user may leave a step, written as e.g. `"input non-empty"` or
+/// `"language == Rust"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Condition {
+    Always,
+    Never,
+    Answered,
+    InputNonEmpty,
+    LanguageIs(Language),
+}
+
+#[derive(Debug)]
+pub enum DefinitionError {
+    Io(io::Error),
+    Parse(String),
+    UnsupportedFormat(String),
+    Empty,
+    InvalidStep { index: usize, reason: String },
+}
+
+impl TourDefinition {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<TourDefinition, DefinitionError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(DefinitionError::Io)?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => TourDefinition::from_toml(&source),
+            Some("json") => TourDefinition::from_json(&source),
+            _ => Err(DefinitionError::UnsupportedFormat(
+                path.display().to_string(),
+            )),
+        }
+    }
+
+    pub fn from_toml(source: &str) -> Result<TourDefinition, DefinitionError> {
+        toml::from_str(source).map_err(|error| DefinitionError::Parse(error.to_string()))
+    }
+
+    pub fn from_json(source: &str) -> Result<TourDefinition, DefinitionError> {
+        serde_json::from_str(source).map_err(|error| DefinitionError::Parse(error.to_string()))
+    }
+
+    pub fn validate(&self) -> Result<(), DefinitionError> {
+        if self.steps.is_empty() {
+            return Err(DefinitionError::Empty);
+        }
+
+        let mut ids = HashSet::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            if let Some(id) = &step.id {
+                if !ids.insert(id.as_str()) {
+                    return Err(DefinitionError::InvalidStep {
+                        index,
+                        reason: format!("the id `{}` is already taken", id),
+                    });
+                }
+            }
+        }
+
+        let last = self.steps.len() - 1;
+        for (index, step) in self.steps.iter().enumerate() {
+            let invalid = |reason: String| DefinitionError::InvalidStep { index, reason };
+
+            if step.kind == StepKind::Page && step.title.is_none() {
+                return Err(invalid("a `page` step needs a title".to_string()));
+            }
+            if step.kind == StepKind::End && index != last {
+                return Err(invalid("an `end` step must be the last step".to_string()));
+            }
+            if step.kind != StepKind::TextInput && !step.validate.is_empty() {
+                return Err(invalid(
+                    "only `text_input` steps can be validated".to_string(),
+                ));
+            }
+
+            let conditions = step
+                .continue_when
+                .iter()
+                .chain(step.next.iter().filter_map(|branch| branch.when.as_ref()));
+            for condition in conditions {
+                condition
+                    .check(step.kind)
+                    .map_err(|reason| invalid(reason.to_string()))?;
+            }
+
+            for branch in &step.next {
+                if !ids.contains(branch.goto.as_str()) {
+                    return Err(invalid(format!("no step has the id `{}`", branch.goto)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn into_steps(self) -> Result<Vec<Box<dyn Step<Message = StepMessage>>>, DefinitionError> {
+        self.validate()?;
+
+        Ok(self
+            .steps
+            .into_iter()
+            .map(StepDefinition::into_step)
+            .collect())
+    }
+}
+
+impl StepDefinition {
+    pub fn into_step(self) -> Box<dyn Step<Message = StepMessage>> {
+        let StepDefinition {
+            id,
+            kind,
+            mut title,
+            mut body,
+            continue_when,
+            validate,
+            next,
+        } = self;
+
+        let step: Box<dyn Step<Message = StepMessage>> = match kind {
+            StepKind::Page => Box::new(PageStep::new(
+                title.take().unwrap_or_default(),
+                mem::take(&mut body),
+            )),
+            StepKind::TextInput => Box::new(TextInputStep::default().with_validators(validate)),
+            kind => builtin::new(kind),
+        };
+        if id.is_none()
+            && title.is_none()
+            && body.is_empty()
+            && continue_when.is_none()
+            && next.is_empty()
+        {
+            return step;
+        }
+
+        let mut step = Configured::new(step).with_body(body);
+        if let Some(id) = id {
+            step = step.with_id(id);
+        }
+        if let Some(title) = title {
+            step = step.with_title(title);
+        }
+        if let Some(condition) = continue_when {
+            step = step.continue_when(condition);
+        }
+        for branch in next {
+            step = step.branch(branch.when, branch.goto);
+        }
+        Box::new(step)
+    }
+}
+
+impl Condition {
+    pub fn is_met(&self, answer: Option<Answer>) -> bool {
+        match self {
+            Condition::Always => true,
+            Condition::Never => false,
+            Condition::Answered => answer.is_some(),
+            Condition::InputNonEmpty => {
+                matches!(answer, Some(Answer::Text(text)) if !text.is_empty())
+            }
+            Condition::LanguageIs(language) => answer == Some(Answer::Language(*language)),
+        }
+    }
+
+    /// Whether the condition makes sense on a step of the given kind.
+    fn check(&self, kind: StepKind) -> Result<(), &'static str> {
+        match self {
+            Condition::Answered if kind != StepKind::TextInput && kind != StepKind::Radio => {
+                Err("`answered` only applies to `text_input` and `radio` steps")
+            }
+            Condition::InputNonEmpty if kind != StepKind::TextInput => {
+                Err("`input non-empty` only applies to `text_input` steps")
+            }
+            Condition::LanguageIs(_) if kind != StepKind::Radio => {
+                Err("`language == ...` only applies to `radio` steps")
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(condition: &str) -> Result<Condition, Self::Err> {
+        let condition = condition.trim();
+
+        match condition {
+            "always" => Ok(Condition::Always),
+            "never" => Ok(Condition::Never),
+            "answered" => Ok(Condition::Answered),
+            "input non-empty" => Ok(Condition::InputNonEmpty),
+            _ => match condition.strip_prefix("language") {
+                Some(rest) => match rest.trim_start().strip_prefix("==") {
+                    Some(language) => language.trim().parse().map(Condition::LanguageIs),
+                    None => Err(format!("unknown condition `{}`", condition)),
+                },
+                None => Err(format!("unknown condition `{}`", condition)),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = String;
+
+    fn try_from(condition: String) -> Result<Condition, Self::Error> {
+        condition.parse()
+    }
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionError::Io(error) => write!(f, "could not read tour definition: {}", error),
+            DefinitionError::Parse(error) => write!(f, "invalid tour definition: {}", error),
+            DefinitionError::UnsupportedFormat(path) => {
+                write!(f, "{} is neither a .toml nor a .json file", path)
+            }
+            DefinitionError::Empty => write!(f, "a tour needs at least one step"),
+            DefinitionError::InvalidStep { index, reason } => {
+                write!(f, "steps[{}]: {}", index, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOUR: &str = r#"
+        [[steps]]
+        kind = "page"
+        title = "Welcome"
+        body = ["Hello!"]
+
+        [[steps]]
+        kind = "radio"
+        continue_when = "language == Rust"
+
+        [[steps]]
+        kind = "end"
+    "#;
+
+    #[test]
+    fn test_from_toml() {
+        let definition = TourDefinition::from_toml(TOUR).unwrap();
+        assert_eq!(definition.steps.len(), 3);
+        assert_eq!(definition.steps[0].kind, StepKind::Page);
+        assert_eq!(definition.steps[0].body, vec!["Hello!".to_string()]);
+        assert_eq!(
+            definition.steps[1].continue_when,
+            Some(Condition::LanguageIs(Language::Rust))
+        );
+        assert!(definition.validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_json() {
+        let definition = TourDefinition::from_json(
+            r#"{ "steps": [{ "kind": "text_input", "continue_when": "input non-empty" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            definition.steps[0].continue_when,
+            Some(Condition::InputNonEmpty)
+        );
+    }
+
+    #[test]
+    fn test_condition() {
+        assert_eq!("always".parse::<Condition>(), Ok(Condition::Always));
+        assert_eq!(
+            "language==elm".parse::<Condition>(),
+            Ok(Condition::LanguageIs(Language::Elm))
+        );
+        assert!("language == Cobol".parse::<Condition>().is_err());
+        assert!("input empty".parse::<Condition>().is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let definition = TourDefinition::from_toml(
+            r#"
+            [[steps]]
+            kind = "slider"
+
+            [[steps]]
+            kind = "slider"
+            continue_when = "input non-empty"
+            "#,
+        )
+        .unwrap();
+
+        match definition.validate() {
+            Err(DefinitionError::InvalidStep { index, .. }) => assert_eq!(index, 1),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_end() {
+        let definition = TourDefinition::from_toml(
+            r#"
+            [[steps]]
+            kind = "end"
+
+            [[steps]]
+            kind = "debugger"
+            "#,
+        )
+        .unwrap();
+
+        match definition.validate() {
+            Err(DefinitionError::InvalidStep { index, .. }) => assert_eq!(index, 0),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_input() {
+        let definition = TourDefinition::from_toml(
+            r#"
+            [[steps]]
+            kind = "text_input"
+
+            [[steps.validate]]
+            rule = "email"
+
+            [[steps]]
+            kind = "radio"
+
+            [[steps.validate]]
+            rule = "min_length"
+            length = 1
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(definition.steps[0].validate.len(), 1);
+        match definition.validate() {
+            Err(DefinitionError::InvalidStep { index, .. }) => assert_eq!(index, 1),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_branches() {
+        let definition = TourDefinition::from_toml(
+            r#"
+            [[steps]]
+            kind = "radio"
+            continue_when = "answered"
+
+            [[steps.next]]
+            when = "language == Rust"
+            goto = "rust"
+
+            [[steps.next]]
+            goto = "end"
+
+            [[steps]]
+            id = "rust"
+            kind = "page"
+            title = "Rust"
+
+            [[steps]]
+            id = "end"
+            kind = "end"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            definition.steps[0].next,
+            vec![
+                Branch {
+                    when: Some(Condition::LanguageIs(Language::Rust)),
+                    goto: "rust".to_string(),
+                },
+                Branch {
+                    when: None,
+                    goto: "end".to_string(),
+                },
+            ]
+        );
+        assert!(definition.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_branches() {
+        let definition = TourDefinition::from_toml(
+            r#"
+            [[steps]]
+            id = "intro"
+            kind = "page"
+            title = "Intro"
+
+            [[steps]]
+            kind = "slider"
+            next = [{ goto = "outro" }]
+            "#,
+        )
+        .unwrap();
+
+        match definition.validate() {
+            Err(DefinitionError::InvalidStep { index, .. }) => assert_eq!(index, 1),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Japanese,
+}
+
+impl Locale {
+    pub fn all() -> [Locale; 2] {
+        [Locale::English, Locale::Japanese]
+    }
+
+    /// The locale's name, written in that locale so anyone can find theirs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::Japanese => "日本語",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../locales/en.txt"),
+            Locale::Japanese => include_str!("../locales/ja.txt"),
+        }
+    }
+}
+
+/// The translated strings of one locale, read from `locales/<code>.txt`.
+///
+/// Each line of those files is `key = value`; empty lines and lines starting
+/// with `#` are skipped, and `{name}` in a value is a placeholder.
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    locale: Locale,
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn new(locale: Locale) -> Catalog {
+        Catalog {
+            locale,
+            messages: parse(locale.source()),
+            fallback: parse(Locale::English.source()),
+        }
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Looks `key` up, falling back to English and then to `key` itself, so
+    /// that text which is not a key, like a title from a tour definition,
+    /// shows up as written.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Like `text`, with each `{name}` replaced by its value in `args`.
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter()
+            .fold(self.text(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog::new(Locale::English)
+    }
+}
+
+fn parse(source: &str) -> HashMap<String, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next()?.trim();
+            let value = parts.next()?.trim();
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let catalog = Catalog::new(Locale::Japanese);
+        assert_eq!(catalog.text("tour.next"), "次へ");
+        assert_eq!(catalog.text("Welcome aboard!"), "Welcome aboard!");
+    }
+
+    #[test]
+    fn test_format() {
+        let catalog = Catalog::default();
+        assert_eq!(
+            catalog.format("tour.progress", &[("current", "2"), ("total", "9")]),
+            "Step 2 of 9"
+        );
+    }
+
+    #[test]
+    fn test_every_locale_is_complete() {
+        let english = parse(Locale::English.source());
+
+        for locale in Locale::all().iter() {
+            let messages = parse(locale.source());
+            for key in english.keys() {
+                assert!(
+                    messages.contains_key(key),
+                    "{:?} is missing `{}`",
+                    locale,
+                    key
+                );
+            }
+        }
+    }
+}
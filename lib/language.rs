@@ -1,4 +1,8 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Language {
     Rust,
     Elm,
@@ -19,6 +23,18 @@ impl Language {
             Language::Other,
         ]
     }
+
+    /// The catalog key of the language's name.
+    pub fn key(self) -> &'static str {
+        match self {
+            Language::Rust => "language.rust",
+            Language::Elm => "language.elm",
+            Language::Ruby => "language.ruby",
+            Language::Haskell => "language.haskell",
+            Language::C => "language.c",
+            Language::Other => "language.other",
+        }
+    }
 }
 
 impl From<Language> for String {
@@ -33,3 +49,15 @@ impl From<Language> for String {
         })
     }
 }
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Language, Self::Err> {
+        Language::all()
+            .iter()
+            .copied()
+            .find(|language| String::from(*language).eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown language `{}`", name))
+    }
+}
@@ -0,0 +1,61 @@
+/// A rough measure of how hard a password is to guess, from its length and
+/// the kinds of characters it mixes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    Weak,
+    Fair,
+    Good,
+    Strong,
+}
+
+impl Strength {
+    pub fn of(password: &str) -> Strength {
+        let length = password.chars().count();
+        let kinds = [
+            password.chars().any(char::is_lowercase),
+            password.chars().any(char::is_uppercase),
+            password.chars().any(|c| c.is_ascii_digit()),
+            password.chars().any(|c| !c.is_alphanumeric()),
+        ]
+        .iter()
+        .filter(|has| **has)
+        .count();
+
+        match (length, kinds) {
+            (0..=7, _) | (_, 0..=1) => Strength::Weak,
+            (8..=11, 2) => Strength::Fair,
+            (8..=11, _) | (_, 2) => Strength::Good,
+            _ => Strength::Strong,
+        }
+    }
+
+    /// Where the strength falls between 0 (weak) and 3 (strong).
+    pub fn level(self) -> f32 {
+        self as u8 as f32
+    }
+
+    /// The catalog key of the strength's name.
+    pub fn key(self) -> &'static str {
+        match self {
+            Strength::Weak => "password.weak",
+            Strength::Fair => "password.fair",
+            Strength::Good => "password.good",
+            Strength::Strong => "password.strong",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strength() {
+        assert_eq!(Strength::of(""), Strength::Weak);
+        assert_eq!(Strength::of("Ab1!"), Strength::Weak);
+        assert_eq!(Strength::of("icecubes123"), Strength::Fair);
+        assert_eq!(Strength::of("Icecubes123"), Strength::Good);
+        assert_eq!(Strength::of("icecubes-and-igloos"), Strength::Good);
+        assert_eq!(Strength::of("Ice-cubes-and-1-igloo"), Strength::Strong);
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::step::Answer;
+
+const VERSION: u32 = 2;
+
+/// The progress of a tour, saved so that it can be resumed after a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    /// The titles of the steps, to tell whether the snapshot belongs to the
+    /// tour being resumed.
+    pub titles: Vec<String>,
+    pub path: Vec<usize>,
+    pub answers: Vec<Option<Answer>>,
+    pub debug: bool,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    Corrupt(serde_json::Error),
+    Incompatible(String),
+}
+
+impl Snapshot {
+    pub fn new(
+        titles: Vec<String>,
+        path: Vec<usize>,
+        answers: Vec<Option<Answer>>,
+        debug: bool,
+    ) -> Snapshot {
+        Snapshot {
+            version: VERSION,
+            titles,
+            path,
+            answers,
+            debug,
+        }
+    }
+
+    /// Where the snapshot of the tour called `name` lives, under the user's
+    /// config directory.
+    pub fn path(name: &str) -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("ice-cube").join(format!("{}.json", name)))
+    }
+
+    /// Reads a snapshot, or `None` if nothing was saved yet.
+    pub fn load(path: &Path) -> Result<Option<Snapshot>, SnapshotError> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(SnapshotError::Io(error)),
+        };
+        let snapshot: Snapshot = serde_json::from_str(&source).map_err(SnapshotError::Corrupt)?;
+
+        if snapshot.version != VERSION {
+            return Err(SnapshotError::Incompatible(format!(
+                "version {} is not supported",
+                snapshot.version
+            )));
+        }
+        Ok(Some(snapshot))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SnapshotError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(SnapshotError::Io)?;
+        }
+
+        // Write next to the snapshot first so a crash never leaves half of it.
+        let json = serde_json::to_string_pretty(self).map_err(SnapshotError::Corrupt)?;
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, json).map_err(SnapshotError::Io)?;
+        fs::rename(&partial, path).map_err(SnapshotError::Io)
+    }
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(error) => write!(f, "could not access snapshot: {}", error),
+            SnapshotError::Corrupt(error) => write!(f, "snapshot is corrupt: {}", error),
+            SnapshotError::Incompatible(reason) => {
+                write!(f, "snapshot does not fit this tour: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::language::Language;
+
+    fn path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("ice-cube-{}", std::process::id()))
+            .join(format!("{}.json", name))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = path("round-trip");
+        let snapshot = Snapshot::new(
+            vec!["Radio button".to_string(), "Text input".to_string()],
+            vec![0, 1],
+            vec![
+                Some(Answer::Language(Language::Rust)),
+                Some(Answer::Text("Ferris".to_string())),
+            ],
+            true,
+        );
+
+        snapshot.save(&path).unwrap();
+        assert_eq!(Snapshot::load(&path).unwrap(), Some(snapshot));
+    }
+
+    #[test]
+    fn test_missing() {
+        assert_eq!(Snapshot::load(&path("missing")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_corrupt() {
+        let path = path("corrupt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{ \"version\": 2, \"pa").unwrap();
+
+        assert!(matches!(
+            Snapshot::load(&path),
+            Err(SnapshotError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn test_other_version() {
+        let path = path("other-version");
+        let mut snapshot = Snapshot::new(Vec::new(), vec![0], Vec::new(), false);
+        snapshot.version = VERSION + 1;
+        snapshot.save(&path).unwrap();
+
+        assert!(matches!(
+            Snapshot::load(&path),
+            Err(SnapshotError::Incompatible(_))
+        ));
+    }
+}
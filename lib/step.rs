@@ -1,103 +1,151 @@
-use iced::{
-    slider, text_input, Checkbox, Color, Column, Element, HorizontalAlignment, Length, Radio, Row,
-    Slider, Text, TextInput,
-};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
 
+use iced::Element;
+use serde::{Deserialize, Serialize};
+use widgets::Theme;
+
+use crate::i18n::Catalog;
 use crate::language::Language;
-use crate::layout::Layout;
-use crate::step_message::StepMessage;
-
-pub enum Step {
-    TextInput {
-        value: String,
-        is_secure: bool,
-        state: text_input::State,
-    },
-    Debugger,
-    End,
+
+/// A page of a tour.
+///
+/// Steps only render their own content: the tour draws the title above it
+/// and the navigation controls below it.
+pub trait Step {
+    type Message;
+
+    /// The title, or the catalog key of the title.
+    fn title(&self) -> &str;
+
+    fn update(&mut self, message: Self::Message, debug: &mut bool);
+
+    fn view(&mut self, context: &Context) -> Element<Self::Message>;
+
+    fn can_continue(&self) -> bool;
+
+    /// Moves keyboard focus to the step's main input, or away from it.
+    fn focus(&mut self, _focused: bool) {}
+
+    /// Whether `message` asks to move on, like pressing Enter in an input.
+    fn is_submit(&self, _message: &Self::Message) -> bool {
+        false
+    }
+
+    /// A name other steps can jump to.
+    fn id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Which step comes after this one, given what the user answered.
+    fn successor(&self) -> Successor {
+        Successor::Next
+    }
+
+    /// What the user answered on this step, for steps that ask something.
+    fn answer(&self) -> Option<Answer> {
+        None
+    }
+
+    /// Whether the answer must stay out of snapshots, which are saved in
+    /// plain text.
+    fn is_sensitive(&self) -> bool {
+        false
+    }
+
+    /// Puts back an answer from a saved snapshot; `None` clears it.
+    fn restore(&mut self, _answer: Option<Answer>) {}
+}
+
+/// What steps are rendered with, besides their own state.
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub debug: bool,
+    pub catalog: Catalog,
+    pub theme: Theme,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Successor {
+    /// The step right after this one in the tour.
+    Next,
+    /// The step with the given id.
+    Goto(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Answer {
+    Text(String),
+    Language(Language),
 }
 
-impl<'a> Step {
-    pub fn update(&mut self, msg: StepMessage, debug: &mut bool) {
-        println!("Step#update {:?}", msg);
-        match msg {
-            StepMessage::InputChanged(new_value) => {
-                if let Step::TextInput { value, .. } = self {
-                    *value = new_value;
-                }
-            }
-            StepMessage::ToggleSecureInput(toggle) => {
-                if let Step::TextInput { is_secure, .. } = self {
-                    *is_secure = toggle;
-                }
-            }
-            _ => {}
-        };
-    }
-
-    pub fn view(&mut self, debug: bool) -> Element<StepMessage> {
-        match self {
-            Step::TextInput {
-                value,
-                is_secure,
-                state,
-            } => {
-                println!("step#view");
-                Self::text_input(value, state)
-            }
-            _ => panic!("FACK"),
+/// Lets a step run inside a tour whose message type wraps the step's own,
+/// e.g. a built-in step next to an application's pages.
+pub struct Embedded<S, Message> {
+    step: S,
+    message: PhantomData<Message>,
+}
+
+impl<S, Message> Embedded<S, Message> {
+    pub fn new(step: S) -> Self {
+        Self {
+            step,
+            message: PhantomData,
         }
-        .into()
     }
+}
 
-    pub fn title(&self) -> &str {
-        match self {
-            _ => "FACK",
-            Step::TextInput { .. } => "Text input",
-            Step::Debugger => "Debugger",
-            Step::End => "End",
-        }
+impl<S, Message> Step for Embedded<S, Message>
+where
+    S: Step,
+    S::Message: TryFrom<Message> + 'static,
+    Message: From<S::Message> + Clone + 'static,
+{
+    type Message = Message;
+
+    fn title(&self) -> &str {
+        self.step.title()
     }
 
-    pub fn can_continue(&self) -> bool {
-        match self {
-            Step::TextInput { value, .. } => !value.is_empty(),
-            Step::Debugger => true,
-            Step::End => false,
-            _ => false,
+    fn update(&mut self, message: Message, debug: &mut bool) {
+        if let Ok(message) = S::Message::try_from(message) {
+            self.step.update(message, debug);
         }
     }
 
-    pub fn container(title: &str) -> Column<'a, StepMessage> {
-        Column::new().spacing(20).push(Text::new(title).size(50))
+    fn view(&mut self, context: &Context) -> Element<Message> {
+        self.step.view(context).map(Message::from)
     }
 
-    pub fn text_input(value: &str, state: &'a mut text_input::State) -> Column<'a, StepMessage> {
-        println!("view > text_input");
-        let text_input = TextInput::new(
-            state,
-            "Type something to continue...",
-            value,
-            StepMessage::InputChanged,
-        )
-        .padding(10)
-        .size(30);
+    fn can_continue(&self) -> bool {
+        self.step.can_continue()
+    }
 
-        Column::new()
-            .spacing(20)
-            .push(Text::new("FACK").size(50))
-            .push(text_input)
-            .push(Text::new(value))
+    fn focus(&mut self, focused: bool) {
+        self.step.focus(focused);
     }
-}
 
-fn color_slider(
-    state: &mut slider::State,
-    component: f32,
-    update: impl Fn(f32) -> Color + 'static,
-) -> Slider<f64, StepMessage> {
-    Slider::new(state, 0.0..=1.0, f64::from(component), move |c| {
-        StepMessage::TextColorChanged(update(c as f32))
-    })
-    .step(0.01)
+    fn is_submit(&self, message: &Message) -> bool {
+        S::Message::try_from(message.clone()).map_or(false, |message| self.step.is_submit(&message))
+    }
+
+    fn id(&self) -> Option<&str> {
+        self.step.id()
+    }
+
+    fn successor(&self) -> Successor {
+        self.step.successor()
+    }
+
+    fn answer(&self) -> Option<Answer> {
+        self.step.answer()
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.step.is_sensitive()
+    }
+
+    fn restore(&mut self, answer: Option<Answer>) {
+        self.step.restore(answer);
+    }
 }
@@ -13,6 +13,7 @@ pub enum StepMessage {
     LanguageSelected(Language),
     ImageWidthChanged(u16),
     InputChanged(String),
+    InputSubmitted,
     ToggleSecureInput(bool),
     DebugToggled(bool),
 }
@@ -1,59 +1,292 @@
-use iced::{slider, text_input, Color, Element};
+use std::collections::HashSet;
+use std::path::Path;
 
-use crate::layout::Layout;
-use crate::step::Step;
+use iced::{Column, Element, Text};
+
+use crate::builtin::{self, PageStep};
+use crate::configured::Configured;
+use crate::definition::{Condition, DefinitionError, StepKind, TourDefinition};
+use crate::language::Language;
+use crate::snapshot::{Snapshot, SnapshotError};
+use crate::step::{Context, Step, Successor};
 use crate::step_message::StepMessage;
 
-pub struct Steps {
-    steps: Vec<Step>,
-    current: usize,
+pub struct Steps<Message = StepMessage> {
+    steps: Vec<Box<dyn Step<Message = Message>>>,
+    /// The steps taken to reach the current one, which comes last.
+    path: Vec<usize>,
+    visited: HashSet<usize>,
 }
 
 impl Steps {
     pub fn new() -> Steps {
+        let mut steps: Vec<_> = vec![StepKind::Slider, StepKind::RowsAndColumns, StepKind::Text]
+            .into_iter()
+            .map(builtin::new)
+            .collect();
+
+        steps.push(Box::new(
+            Configured::new(builtin::new(StepKind::Radio))
+                .continue_when(Condition::Answered)
+                .branch(Some(Condition::LanguageIs(Language::Rust)), "rust")
+                .branch(None, "image"),
+        ));
+        steps.push(Box::new(
+            Configured::new(Box::new(PageStep::new(
+                "rust.title".to_string(),
+                vec!["rust.intro".to_string(), "rust.secret".to_string()],
+            )))
+            .with_id("rust"),
+        ));
+        steps.push(Box::new(
+            Configured::new(builtin::new(StepKind::Image)).with_id("image"),
+        ));
+        steps.extend(
+            vec![StepKind::TextInput, StepKind::Debugger, StepKind::End]
+                .into_iter()
+                .map(builtin::new),
+        );
+
+        Steps::with_steps(steps)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Steps, DefinitionError> {
+        Ok(Steps::with_steps(
+            TourDefinition::from_file(path)?.into_steps()?,
+        ))
+    }
+}
+
+impl<Message: 'static> Steps<Message> {
+    pub fn with_steps(steps: Vec<Box<dyn Step<Message = Message>>>) -> Steps<Message> {
         Steps {
-            steps: vec![
-                Step::TextInput {
-                    value: String::new(),
-                    is_secure: false,
-                    state: text_input::State::new(),
-                },
-                Step::Debugger,
-                Step::End,
-            ],
-            current: 0,
+            steps,
+            path: vec![0],
+            visited: vec![0].into_iter().collect(),
         }
     }
 
-    pub fn update(&mut self, msg: StepMessage, debug: &mut bool) {
-        self.steps[self.current].update(msg, debug);
+    pub fn push(&mut self, step: impl Step<Message = Message> + 'static) {
+        self.steps.push(Box::new(step));
     }
 
-    pub fn view(&mut self, debug: bool) -> Element<StepMessage> {
-        self.steps[self.current].view(debug)
+    pub fn update(&mut self, msg: Message, debug: &mut bool) {
+        let current = self.current();
+        self.steps[current].update(msg, debug);
+    }
+
+    pub fn focus(&mut self, focused: bool) {
+        let current = self.current();
+        self.steps[current].focus(focused);
+    }
+
+    pub fn is_submit(&self, msg: &Message) -> bool {
+        self.steps[self.current()].is_submit(msg)
+    }
+
+    pub fn view(&mut self, context: &Context) -> Element<Message> {
+        let current = self.current();
+        let step = &mut self.steps[current];
+
+        Column::new()
+            .spacing(20)
+            .push(Text::new(context.catalog.text(step.title())).size(50))
+            .push(step.view(context))
+            .into()
     }
 
     pub fn advance(&mut self) {
-        if self.can_continue() {
-            self.current += 1;
+        if !self.steps[self.current()].can_continue() {
+            return;
+        }
+        if let Some(next) = self.next() {
+            self.path.push(next);
+            self.visited.insert(next);
+        }
+    }
+
+    /// Goes straight to a step the user has already been to.
+    pub fn jump_to(&mut self, index: usize) {
+        if let Some(position) = self.path.iter().position(|step| *step == index) {
+            self.path.truncate(position + 1);
+        } else if self.visited.contains(&index) {
+            self.path.push(index);
         }
     }
 
+    pub fn is_visited(&self, index: usize) -> bool {
+        self.visited.contains(&index)
+    }
+
+    /// Returns to the step the user came from, which is not necessarily the
+    /// one before the current step in the tour.
     pub fn go_back(&mut self) {
         if self.has_previous() {
-            self.current -= 1;
+            self.path.pop();
         }
     }
 
     pub fn has_previous(&self) -> bool {
-        self.current > 0
+        self.path.len() > 1
     }
 
     pub fn can_continue(&self) -> bool {
-        self.current + 1 < self.steps.len() && self.steps[self.current].can_continue()
+        self.steps[self.current()].can_continue() && self.next().is_some()
     }
 
+    /// The title of the current step, which may be a catalog key.
     pub fn title(&self) -> &str {
-        self.steps[self.current].title()
+        self.steps[self.current()].title()
+    }
+
+    pub fn titles(&self) -> impl Iterator<Item = &str> {
+        self.steps.iter().map(|step| step.title())
+    }
+
+    /// The index of the step being shown.
+    pub fn current(&self) -> usize {
+        self.path[self.path.len() - 1]
+    }
+
+    pub fn snapshot(&self, debug: bool) -> Snapshot {
+        Snapshot::new(
+            self.titles().map(str::to_string).collect(),
+            self.path.clone(),
+            self.steps
+                .iter()
+                .map(|step| {
+                    if step.is_sensitive() {
+                        None
+                    } else {
+                        step.answer()
+                    }
+                })
+                .collect(),
+            debug,
+        )
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        if !snapshot.titles.iter().map(String::as_str).eq(self.titles()) {
+            return Err(SnapshotError::Incompatible(
+                "it was saved by a different tour".to_string(),
+            ));
+        }
+        if snapshot.path.is_empty()
+            || snapshot.path.iter().any(|index| *index >= self.steps.len())
+            || snapshot.answers.len() != self.steps.len()
+        {
+            return Err(SnapshotError::Incompatible(
+                "its progress is out of range".to_string(),
+            ));
+        }
+
+        for (step, answer) in self.steps.iter_mut().zip(&snapshot.answers) {
+            step.restore(answer.clone());
+        }
+        self.path = snapshot.path.clone();
+        self.visited = self.path.iter().copied().collect();
+        Ok(())
+    }
+
+    /// Goes back to the first step and forgets every answer.
+    pub fn restart(&mut self) {
+        for step in &mut self.steps {
+            step.restore(None);
+        }
+        self.path = vec![0];
+        self.visited = vec![0].into_iter().collect();
+    }
+
+    fn next(&self) -> Option<usize> {
+        let current = self.current();
+
+        match self.steps[current].successor() {
+            Successor::Next => Some(current + 1).filter(|next| *next < self.steps.len()),
+            Successor::Goto(id) => self
+                .steps
+                .iter()
+                .position(|step| step.id() == Some(id.as_str())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_radio() -> Steps {
+        let mut steps = Steps::new();
+        steps.advance();
+        steps.advance();
+        steps.advance();
+        assert_eq!(steps.title(), "radio.title");
+        steps
+    }
+
+    #[test]
+    fn test_branch() {
+        let mut steps = at_radio();
+        assert!(!steps.can_continue());
+
+        steps.update(StepMessage::LanguageSelected(Language::Elm), &mut false);
+        steps.advance();
+        assert_eq!(steps.title(), "image.title");
+
+        steps.go_back();
+        steps.update(StepMessage::LanguageSelected(Language::Rust), &mut false);
+        steps.advance();
+        assert_eq!(steps.title(), "rust.title");
+        steps.advance();
+        assert_eq!(steps.title(), "image.title");
+    }
+
+    #[test]
+    fn test_go_back_retraces_path() {
+        let mut steps = at_radio();
+        steps.update(StepMessage::LanguageSelected(Language::C), &mut false);
+        steps.advance();
+        assert_eq!(steps.title(), "image.title");
+
+        steps.go_back();
+        assert_eq!(steps.title(), "radio.title");
+    }
+
+    #[test]
+    fn test_jump_to() {
+        let mut steps = at_radio();
+        assert!(!steps.is_visited(4));
+
+        steps.jump_to(1);
+        assert_eq!(steps.title(), "rows_and_columns.title");
+        steps.jump_to(3);
+        assert_eq!(steps.title(), "radio.title");
+        steps.jump_to(4);
+        assert_eq!(steps.title(), "radio.title");
+    }
+
+    #[test]
+    fn test_snapshot_leaves_out_passwords() {
+        let mut steps = at_radio();
+        steps.update(StepMessage::LanguageSelected(Language::Elm), &mut false);
+        steps.advance();
+        steps.advance();
+        assert_eq!(steps.title(), "text_input.title");
+
+        steps.update(StepMessage::InputChanged("hunter2".to_string()), &mut false);
+        steps.update(StepMessage::ToggleSecureInput(true), &mut false);
+        assert!(steps.can_continue());
+        assert_eq!(steps.snapshot(false).answers[steps.current()], None);
+
+        steps.update(StepMessage::ToggleSecureInput(false), &mut false);
+        assert!(steps.snapshot(false).answers[steps.current()].is_some());
+    }
+
+    #[test]
+    fn test_restart() {
+        let mut steps = at_radio();
+        steps.restart();
+        assert!(!steps.has_previous());
+        assert_eq!(steps.title(), "slider.title");
     }
 }
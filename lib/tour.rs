@@ -1,52 +1,121 @@
+use std::fmt::Debug;
+use std::iter;
+use std::path::{Path, PathBuf};
+
 use iced::{
-    button, scrollable, Button, Color, Column, Container, Element, HorizontalAlignment, Length,
-    Row, Sandbox, Scrollable, Space, Text,
+    button, executor, scrollable, Application, Clipboard, Color, Column, Command, Container,
+    Element, Length, ProgressBar, Row, Scrollable, Space, Subscription, Text,
 };
+use iced_native::keyboard::{self, KeyCode};
+use iced_native::{event, subscription, Event};
+use widgets::{button, card, toolbar, Size, Variant};
 
-use crate::step_message::StepMessage;
-use crate::steps::Steps;
+pub use crate::configured::Configured;
+pub use crate::definition::{Condition, DefinitionError, StepDefinition, StepKind, TourDefinition};
+pub use crate::i18n::{Catalog, Locale};
+pub use crate::language::Language;
+pub use crate::layout::Layout;
+pub use crate::snapshot::{Snapshot, SnapshotError};
+pub use crate::step::{Answer, Context, Embedded, Step};
+pub use crate::step_message::StepMessage;
+pub use crate::steps::Steps;
+pub use crate::validator::{Rule, Validator};
+pub use widgets::{Palette, Theme};
 
+pub mod builtin;
+mod configured;
+mod definition;
+mod i18n;
 mod language;
 mod layout;
+mod password;
+mod snapshot;
 mod step;
 mod step_message;
 mod steps;
-mod style;
+mod validator;
 
-pub struct Tour {
-    steps: Steps,
+/// Runs a tour of `Steps`, whose pages all speak `M`.
+pub struct Tour<M = StepMessage> {
+    steps: Steps<M>,
     scroll: scrollable::State,
     back_button: button::State,
     next_button: button::State,
-    debug: bool,
+    start_over_button: button::State,
+    breadcrumb: Vec<button::State>,
+    locale_buttons: Vec<button::State>,
+    theme_button: button::State,
+    context: Context,
+    snapshot: Option<PathBuf>,
+    saved: Option<Snapshot>,
+}
+
+pub struct Flags<M = StepMessage> {
+    pub steps: Steps<M>,
+    /// Where progress is saved to and resumed from, if anywhere.
+    pub snapshot: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
-pub enum Message {
+pub enum Message<M = StepMessage> {
     BackPressed,
     NextPressed,
-    StepMessage(StepMessage),
+    StartOver,
+    JumpTo(usize),
+    LocaleSelected(Locale),
+    ThemeToggled,
+    FocusInput,
+    Unfocus,
+    StepMessage(M),
 }
 
-impl Sandbox for Tour {
-    type Message = Message;
+impl<M> Application for Tour<M>
+where
+    M: Debug + Clone + Send + 'static,
+{
+    type Executor = executor::Default;
+    type Message = Message<M>;
+    type Flags = Flags<M>;
 
-    fn new() -> Self {
-        Self {
-            steps: Steps::new(),
+    fn new(flags: Flags<M>) -> (Self, Command<Message<M>>) {
+        let breadcrumb = iter::repeat_with(button::State::new)
+            .take(flags.steps.titles().count())
+            .collect();
+
+        let mut tour = Self {
+            steps: flags.steps,
             scroll: scrollable::State::new(),
             back_button: button::State::new(),
             next_button: button::State::new(),
-            debug: false,
-        }
+            start_over_button: button::State::new(),
+            breadcrumb,
+            locale_buttons: iter::repeat_with(button::State::new)
+                .take(Locale::all().len())
+                .collect(),
+            theme_button: button::State::new(),
+            context: Context::default(),
+            snapshot: flags.snapshot,
+            saved: None,
+        };
+        tour.resume();
+
+        (tour, Command::none())
     }
 
     fn title(&self) -> String {
-        format!("{} - Iced", self.steps.title())
+        let catalog = &self.context.catalog;
+
+        catalog.format(
+            "tour.window_title",
+            &[("title", catalog.text(self.steps.title()))],
+        )
+    }
+
+    fn background_color(&self) -> Color {
+        self.context.theme.palette().background
     }
 
-    fn update(&mut self, event: Message) {
-        println!("update: {:?}", event);
+    fn update(&mut self, event: Message<M>, _clipboard: &mut Clipboard) -> Command<Message<M>> {
         match event {
             Message::BackPressed => {
                 self.steps.go_back();
@@ -54,71 +123,254 @@ impl Sandbox for Tour {
             Message::NextPressed => {
                 self.steps.advance();
             }
+            Message::StartOver => {
+                self.steps.restart();
+                self.context.debug = false;
+            }
+            Message::JumpTo(index) => {
+                self.steps.jump_to(index);
+            }
+            Message::LocaleSelected(locale) => {
+                self.context.catalog = Catalog::new(locale);
+            }
+            Message::ThemeToggled => {
+                self.context.theme = self.context.theme.next();
+            }
+            Message::FocusInput => {
+                self.steps.focus(true);
+            }
+            Message::Unfocus => {
+                self.steps.focus(false);
+            }
             Message::StepMessage(step_msg) => {
-                self.steps.update(step_msg, &mut self.debug);
+                let submit = self.steps.is_submit(&step_msg);
+                self.steps.update(step_msg, &mut self.context.debug);
+                if submit {
+                    self.steps.advance();
+                }
             }
         }
+        self.save();
+        Command::none()
     }
 
-    fn view(&mut self) -> Element<Message> {
-        println!("view");
+    fn subscription(&self) -> Subscription<Message<M>> {
+        subscription::events_with(|event, status| {
+            // Keys a focused input handles itself, like arrows moving its
+            // cursor, are not navigation.
+            if let event::Status::Captured = status {
+                return None;
+            }
+
+            match event {
+                Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => match key_code {
+                    KeyCode::Enter | KeyCode::Right => Some(Message::NextPressed),
+                    KeyCode::Left => Some(Message::BackPressed),
+                    KeyCode::Tab => Some(Message::FocusInput),
+                    KeyCode::Escape => Some(Message::Unfocus),
+                    _ => None,
+                },
+                _ => None,
+            }
+        })
+    }
+
+    fn view(&mut self) -> Element<Message<M>> {
         let Self {
             steps,
             scroll,
             back_button,
             next_button,
+            start_over_button,
+            breadcrumb,
+            locale_buttons,
+            theme_button,
+            context,
             ..
         } = self;
+        let catalog = &context.catalog;
+        let theme = context.theme;
 
-        let mut controls = Row::new();
+        let current = steps.current();
+        let total = breadcrumb.len();
 
-        if steps.has_previous() {
-            controls = controls.push(
-                button(back_button, "Back")
-                    .on_press(Message::BackPressed)
-                    .style(style::Button::Secondary),
+        let locales = Locale::all().iter().zip(locale_buttons.iter_mut()).fold(
+            Row::new().spacing(5),
+            |row, (locale, state)| {
+                row.push(if *locale == catalog.locale() {
+                    button(
+                        state,
+                        locale.name(),
+                        Variant::Primary,
+                        Size::compact(),
+                        theme,
+                    )
+                } else {
+                    button(
+                        state,
+                        locale.name(),
+                        Variant::Secondary,
+                        Size::compact(),
+                        theme,
+                    )
+                    .on_press(Message::LocaleSelected(*locale))
+                })
+            },
+        );
+
+        let toggle_theme = button(
+            theme_button,
+            &catalog.format("tour.theme", &[("theme", catalog.text(theme.key()))]),
+            Variant::Secondary,
+            Size::compact().width(Length::Fill),
+            theme,
+        )
+        .on_press(Message::ThemeToggled);
+
+        let breadcrumb = steps.titles().zip(breadcrumb.iter_mut()).enumerate().fold(
+            Column::new()
+                .spacing(5)
+                .width(Length::Units(180))
+                .push(locales)
+                .push(toggle_theme),
+            |column, (index, (title, state))| {
+                let label = format!("{}. {}", index + 1, catalog.text(title));
+                let size = Size::compact().width(Length::Fill);
+
+                column.push(if index == current {
+                    button(state, &label, Variant::Primary, size, theme)
+                } else if steps.is_visited(index) {
+                    button(state, &label, Variant::Secondary, size, theme)
+                        .on_press(Message::JumpTo(index))
+                } else {
+                    button(state, &label, Variant::Secondary, size, theme)
+                })
+            },
+        );
+
+        let progress = Column::new()
+            .spacing(5)
+            .push(
+                Text::new(catalog.format(
+                    "tour.progress",
+                    &[
+                        ("current", &(current + 1).to_string()),
+                        ("total", &total.to_string()),
+                    ],
+                ))
+                .size(16),
+            )
+            .push(
+                ProgressBar::new(0.0..=total as f32, (current + 1) as f32)
+                    .height(Length::Units(8))
+                    .style(theme),
             );
+
+        let mut controls = toolbar();
+
+        if steps.has_previous() {
+            controls = controls
+                .push(
+                    button(
+                        back_button,
+                        catalog.text("tour.back"),
+                        Variant::Secondary,
+                        Size::regular(),
+                        theme,
+                    )
+                    .on_press(Message::BackPressed),
+                )
+                .push(
+                    button(
+                        start_over_button,
+                        catalog.text("tour.start_over"),
+                        Variant::Secondary,
+                        Size::regular(),
+                        theme,
+                    )
+                    .on_press(Message::StartOver),
+                );
         }
 
         controls = controls.push(Space::with_width(Length::Fill));
 
         if steps.can_continue() {
             controls = controls.push(
-                button(next_button, "Next")
-                    .on_press(Message::NextPressed)
-                    .style(style::Button::Primary),
+                button(
+                    next_button,
+                    catalog.text("tour.next"),
+                    Variant::Primary,
+                    Size::regular(),
+                    theme,
+                )
+                .on_press(Message::NextPressed),
             );
         }
 
         let content: Element<_> = Column::new()
             .max_width(540)
             .spacing(20)
-            .padding(20)
-            .push(steps.view(self.debug).map(Message::StepMessage))
+            .push(progress)
+            .push(card(steps.view(context).map(Message::StepMessage), theme))
             .push(controls)
             .into();
 
-        let content = if self.debug {
-            content.explain(Color::BLACK)
+        let content = if context.debug {
+            content.explain(theme.palette().text)
         } else {
             content
         };
 
-        let scrollable =
-            Scrollable::new(scroll).push(Container::new(content).width(Length::Fill).center_x());
+        let page = Row::new()
+            .spacing(20)
+            .padding(20)
+            .push(breadcrumb)
+            .push(content);
+
+        let scrollable = Scrollable::new(scroll)
+            .push(Container::new(page).width(Length::Fill).center_x())
+            .style(theme);
 
         Container::new(scrollable)
             .height(Length::Fill)
             .center_y()
+            .style(theme)
             .into()
     }
 }
 
-fn button<'a, Message: Clone>(state: &'a mut button::State, label: &str) -> Button<'a, Message> {
-    Button::new(
-        state,
-        Text::new(label).horizontal_alignment(HorizontalAlignment::Center),
-    )
-    .padding(12)
-    .min_width(100)
+impl<M: 'static> Tour<M> {
+    fn resume(&mut self) {
+        if let Some(path) = self.snapshot.clone() {
+            if let Err(error) = self.restore(&path) {
+                eprintln!("starting over, {}: {}", path.display(), error);
+                self.steps.restart();
+            }
+        }
+    }
+
+    fn restore(&mut self, path: &Path) -> Result<(), SnapshotError> {
+        if let Some(snapshot) = Snapshot::load(path)? {
+            self.steps.restore(&snapshot)?;
+            self.context.debug = snapshot.debug;
+            self.saved = Some(snapshot);
+        }
+        Ok(())
+    }
+
+    fn save(&mut self) {
+        let path = match &self.snapshot {
+            Some(path) => path,
+            None => return,
+        };
+
+        let snapshot = self.steps.snapshot(self.context.debug);
+        if self.saved.as_ref() == Some(&snapshot) {
+            return;
+        }
+        if let Err(error) = snapshot.save(path) {
+            eprintln!("{}: {}", path.display(), error);
+        }
+        self.saved = Some(snapshot);
+    }
 }
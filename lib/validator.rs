@@ -0,0 +1,215 @@
+use std::convert::TryFrom;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::i18n::Catalog;
+
+/// A rule a text input must follow, and what to tell the user when it does
+/// not.
+///
+/// In a tour definition, a validator is written as e.g.
+/// `{ rule = "min_length", length = 3 }` or
+/// `{ rule = "integer", min = 1, max = 99, message = "Pick 1 to 99" }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "ValidatorDefinition")]
+pub struct Validator {
+    rule: Rule,
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Rule {
+    MinLength(usize),
+    MaxLength(usize),
+    Pattern(Regex),
+    Email,
+    Integer { min: i64, max: i64 },
+}
+
+impl Validator {
+    pub fn new(rule: Rule) -> Validator {
+        Validator {
+            rule,
+            message: None,
+        }
+    }
+
+    /// Replaces the default error message; it may be a catalog key.
+    pub fn with_message(mut self, message: impl Into<String>) -> Validator {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn check(&self, value: &str) -> bool {
+        match &self.rule {
+            Rule::MinLength(length) => value.chars().count() >= *length,
+            Rule::MaxLength(length) => value.chars().count() <= *length,
+            Rule::Pattern(pattern) => pattern.is_match(value),
+            Rule::Email => is_email(value),
+            Rule::Integer { min, max } => {
+                matches!(value.trim().parse::<i64>(), Ok(number) if *min <= number && number <= *max)
+            }
+        }
+    }
+
+    pub fn error(&self, catalog: &Catalog) -> String {
+        if let Some(message) = &self.message {
+            return catalog.text(message).to_string();
+        }
+
+        match &self.rule {
+            Rule::MinLength(length) => {
+                catalog.format("validator.min_length", &[("length", &length.to_string())])
+            }
+            Rule::MaxLength(length) => {
+                catalog.format("validator.max_length", &[("length", &length.to_string())])
+            }
+            Rule::Pattern(_) => catalog.text("validator.pattern").to_string(),
+            Rule::Email => catalog.text("validator.email").to_string(),
+            Rule::Integer { min, max } => catalog.format(
+                "validator.integer",
+                &[("min", &min.to_string()), ("max", &max.to_string())],
+            ),
+        }
+    }
+}
+
+/// Only catches obvious typos; the address may still not exist.
+fn is_email(value: &str) -> bool {
+    let mut parts = value.splitn(2, '@');
+    let (local, domain) = match (parts.next(), parts.next()) {
+        (Some(local), Some(domain)) => (local, domain),
+        _ => return false,
+    };
+
+    !local.is_empty()
+        && !value.chars().any(char::is_whitespace)
+        && !domain.contains('@')
+        && domain.split('.').count() > 1
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+enum RuleDefinition {
+    MinLength { length: usize },
+    MaxLength { length: usize },
+    Pattern { pattern: String },
+    Email,
+    Integer { min: i64, max: i64 },
+}
+
+#[derive(Deserialize)]
+struct ValidatorDefinition {
+    #[serde(flatten)]
+    rule: RuleDefinition,
+    message: Option<String>,
+}
+
+impl TryFrom<ValidatorDefinition> for Validator {
+    type Error = String;
+
+    fn try_from(definition: ValidatorDefinition) -> Result<Validator, Self::Error> {
+        let rule = match definition.rule {
+            RuleDefinition::MinLength { length } => Rule::MinLength(length),
+            RuleDefinition::MaxLength { length } => Rule::MaxLength(length),
+            RuleDefinition::Pattern { pattern } => Regex::new(&format!("^(?:{})$", pattern))
+                .map(Rule::Pattern)
+                .map_err(|error| format!("invalid pattern `{}`: {}", pattern, error))?,
+            RuleDefinition::Email => Rule::Email,
+            RuleDefinition::Integer { min, max } if min > max => {
+                return Err(format!("the integer range {}..={} is empty", min, max))
+            }
+            RuleDefinition::Integer { min, max } => Rule::Integer { min, max },
+        };
+
+        Ok(Validator {
+            rule,
+            message: definition.message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length() {
+        let min = Validator::new(Rule::MinLength(3));
+        let max = Validator::new(Rule::MaxLength(3));
+
+        assert!(!min.check("ab"));
+        assert!(min.check("氷の塊"));
+        assert!(max.check("abc"));
+        assert!(!max.check("abcd"));
+    }
+
+    #[test]
+    fn test_email() {
+        let email = Validator::new(Rule::Email);
+
+        assert!(email.check("ferris@rust-lang.org"));
+        assert!(!email.check("ferris"));
+        assert!(!email.check("ferris@localhost"));
+        assert!(!email.check("@rust-lang.org"));
+        assert!(!email.check("fer ris@rust-lang.org"));
+        assert!(!email.check("ferris@rust-lang..org"));
+    }
+
+    #[test]
+    fn test_integer() {
+        let age = Validator::new(Rule::Integer { min: 0, max: 150 });
+
+        assert!(age.check("42"));
+        assert!(age.check(" 0 "));
+        assert!(!age.check("-1"));
+        assert!(!age.check("forty-two"));
+    }
+
+    #[test]
+    fn test_from_toml() {
+        #[derive(Deserialize)]
+        struct Input {
+            validate: Vec<Validator>,
+        }
+
+        let input: Input = toml::from_str(
+            r#"
+            [[validate]]
+            rule = "pattern"
+            pattern = "[a-z]+"
+            message = "Lowercase letters only"
+            "#,
+        )
+        .unwrap();
+        let validator = &input.validate[0];
+
+        assert!(validator.check("ferris"));
+        assert!(!validator.check("Ferris"));
+        assert_eq!(
+            validator.error(&Catalog::default()),
+            "Lowercase letters only"
+        );
+        assert!(toml::from_str::<Input>(
+            r#"
+            [[validate]]
+            rule = "integer"
+            min = 2
+            max = 1
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_error() {
+        let catalog = Catalog::default();
+
+        assert_eq!(
+            Validator::new(Rule::MinLength(2)).error(&catalog),
+            "Enter at least 2 characters"
+        );
+    }
+}
@@ -1,10 +1,32 @@
-use iced::{Sandbox, Settings};
+use std::env;
+use std::path::Path;
+use std::process;
 
-use tour_lib::Tour;
+use iced::{Application, Settings};
+
+use tour_lib::{Flags, Snapshot, Steps, Tour};
 
 pub fn main() -> iced::Result {
+    let (steps, name) = match env::args().nth(1) {
+        Some(path) => {
+            let steps = Steps::from_file(&path).unwrap_or_else(|error| {
+                eprintln!("{}: {}", path, error);
+                process::exit(1);
+            });
+            let name = Path::new(&path).file_stem().map_or_else(
+                || "tour".to_string(),
+                |stem| stem.to_string_lossy().into_owned(),
+            );
+            (steps, name)
+        }
+        None => (Steps::new(), "tour".to_string()),
+    };
+
     Tour::run(Settings {
         default_font: Some(include_bytes!("../fonts/Mamelon-5-Hi-Regular.otf")),
-        ..Settings::default()
+        ..Settings::with_flags(Flags {
+            steps,
+            snapshot: Snapshot::path(&name),
+        })
     })
 }
@@ -0,0 +1,89 @@
+use iced::{button, Button, HorizontalAlignment, Length, Text, VerticalAlignment};
+
+use crate::theme::{Theme, Variant};
+
+/// How much room a button takes.
+#[derive(Debug, Clone, Copy)]
+pub struct Size {
+    pub padding: u16,
+    pub width: Length,
+    pub height: Length,
+    pub min_width: u32,
+    pub text_size: Option<u16>,
+}
+
+impl Size {
+    /// At least 100 wide, like the tour's navigation buttons.
+    pub fn regular() -> Size {
+        Size {
+            padding: 12,
+            width: Length::Shrink,
+            height: Length::Shrink,
+            min_width: 100,
+            text_size: None,
+        }
+    }
+
+    /// Tight padding and small text, for lists of buttons.
+    pub fn compact() -> Size {
+        Size {
+            padding: 8,
+            text_size: Some(16),
+            min_width: 0,
+            ..Size::regular()
+        }
+    }
+
+    /// 40 by 30, like the browser's navigation buttons.
+    pub fn icon() -> Size {
+        Size {
+            padding: 10,
+            width: Length::Units(40),
+            height: Length::Units(30),
+            min_width: 0,
+            text_size: None,
+        }
+    }
+
+    pub fn width(mut self, width: Length) -> Size {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Length) -> Size {
+        self.height = height;
+        self
+    }
+}
+
+/// A button with a centered label, styled after `variant`.
+pub fn button<'a, Message: Clone>(
+    state: &'a mut button::State,
+    label: &str,
+    variant: Variant,
+    size: Size,
+    theme: Theme,
+) -> Button<'a, Message> {
+    let mut label = Text::new(label)
+        .horizontal_alignment(HorizontalAlignment::Center)
+        .vertical_alignment(VerticalAlignment::Center);
+    if let Some(text_size) = size.text_size {
+        label = label.size(text_size);
+    }
+
+    Button::new(state, label)
+        .padding(size.padding)
+        .width(size.width)
+        .height(size.height)
+        .min_width(size.min_width)
+        .style(theme.button(variant))
+}
+
+/// A small secondary button showing a single symbol, like "←".
+pub fn icon_button<'a, Message: Clone>(
+    state: &'a mut button::State,
+    icon: &str,
+    theme: Theme,
+) -> Button<'a, Message> {
+    button(state, icon, Variant::Secondary, Size::icon(), theme)
+}
@@ -0,0 +1,14 @@
+use iced::{Container, Element, Length};
+
+use crate::theme::Theme;
+
+/// Sets `content` apart on a padded, rounded surface.
+pub fn card<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    theme: Theme,
+) -> Container<'a, Message> {
+    Container::new(content)
+        .padding(20)
+        .width(Length::Fill)
+        .style(theme.card())
+}
@@ -0,0 +1,13 @@
+use iced::{Column, Text, TextInput};
+
+/// Puts `label` above an input, which keeps its own placeholder, handlers
+/// and style.
+pub fn labeled_input<'a, Message: Clone + 'a>(
+    label: &str,
+    input: TextInput<'a, Message>,
+) -> Column<'a, Message> {
+    Column::new()
+        .spacing(5)
+        .push(Text::new(label).size(16))
+        .push(input)
+}
@@ -0,0 +1,337 @@
+use iced::{
+    button, checkbox, container, progress_bar, radio, scrollable, slider, text_input, Background,
+    Color, Vector,
+};
+
+/// A named set of colors every widget of an app is drawn with.
+///
+/// Widgets that need no variant take the theme itself as their style, e.g.
+/// `Slider::new(...).style(theme)`; buttons take `theme.button(Variant::Primary)`
+/// and cards `theme.card()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub background: Color,
+    /// Behind inputs, checkboxes and the like.
+    pub surface: Color,
+    pub text: Color,
+    pub primary: Color,
+    pub secondary: Color,
+    /// Text on top of `primary` and `secondary`.
+    pub on_accent: Color,
+    pub border: Color,
+    pub error: Color,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    Primary,
+    Secondary,
+}
+
+pub struct ButtonStyle {
+    palette: Palette,
+    variant: Variant,
+}
+
+pub struct CardStyle {
+    palette: Palette,
+    border_width: f32,
+}
+
+impl Theme {
+    pub fn all() -> [Theme; 3] {
+        [Theme::Light, Theme::Dark, Theme::HighContrast]
+    }
+
+    /// The theme after this one, to cycle through them with one button.
+    pub fn next(self) -> Theme {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::HighContrast,
+            Theme::HighContrast => Theme::Light,
+        }
+    }
+
+    /// The catalog key of the theme's name.
+    pub fn key(self) -> &'static str {
+        match self {
+            Theme::Light => "theme.light",
+            Theme::Dark => "theme.dark",
+            Theme::HighContrast => "theme.high_contrast",
+        }
+    }
+
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Light => Palette {
+                background: Color::WHITE,
+                surface: Color::from_rgb(0.95, 0.95, 0.95),
+                text: Color::BLACK,
+                primary: Color::from_rgb(0.11, 0.42, 0.87),
+                secondary: Color::from_rgb(0.5, 0.5, 0.5),
+                on_accent: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+                border: Color::from_rgb(0.7, 0.7, 0.7),
+                error: Color::from_rgb(0.8, 0.2, 0.2),
+            },
+            Theme::Dark => Palette {
+                background: Color::from_rgb8(0x36, 0x39, 0x3F),
+                surface: Color::from_rgb8(0x40, 0x44, 0x4B),
+                text: Color::from_rgb(0.9, 0.9, 0.9),
+                primary: Color::from_rgb8(0x72, 0x89, 0xDA),
+                secondary: Color::from_rgb8(0x5A, 0x5E, 0x66),
+                on_accent: Color::WHITE,
+                border: Color::from_rgb8(0x6A, 0x6E, 0x76),
+                error: Color::from_rgb(1.0, 0.45, 0.45),
+            },
+            Theme::HighContrast => Palette {
+                background: Color::BLACK,
+                surface: Color::BLACK,
+                text: Color::WHITE,
+                primary: Color::from_rgb(1.0, 0.85, 0.0),
+                secondary: Color::WHITE,
+                on_accent: Color::BLACK,
+                border: Color::WHITE,
+                error: Color::from_rgb(1.0, 0.5, 0.5),
+            },
+        }
+    }
+
+    pub fn button(self, variant: Variant) -> ButtonStyle {
+        ButtonStyle {
+            palette: self.palette(),
+            variant,
+        }
+    }
+
+    pub fn card(self) -> CardStyle {
+        CardStyle {
+            palette: self.palette(),
+            border_width: self.border_width(),
+        }
+    }
+
+    /// How thick borders are, so that high contrast can make them stand out.
+    fn border_width(self) -> f32 {
+        match self {
+            Theme::HighContrast => 2.0,
+            _ => 1.0,
+        }
+    }
+}
+
+impl button::StyleSheet for ButtonStyle {
+    fn active(&self) -> button::Style {
+        button::Style {
+            background: Some(Background::Color(match self.variant {
+                Variant::Primary => self.palette.primary,
+                Variant::Secondary => self.palette.secondary,
+            })),
+            border_radius: 12.0,
+            shadow_offset: Vector::new(1.0, 1.0),
+            text_color: self.palette.on_accent,
+            ..button::Style::default()
+        }
+    }
+
+    fn hovered(&self) -> button::Style {
+        button::Style {
+            border_width: 2.0,
+            border_color: self.palette.text,
+            shadow_offset: Vector::new(1.0, 2.0),
+            ..self.active()
+        }
+    }
+}
+
+impl container::StyleSheet for Theme {
+    fn style(&self) -> container::Style {
+        let palette = self.palette();
+
+        container::Style {
+            text_color: Some(palette.text),
+            background: Some(Background::Color(palette.background)),
+            ..container::Style::default()
+        }
+    }
+}
+
+impl container::StyleSheet for CardStyle {
+    fn style(&self) -> container::Style {
+        container::Style {
+            text_color: Some(self.palette.text),
+            background: Some(Background::Color(self.palette.surface)),
+            border_radius: 12.0,
+            border_width: self.border_width,
+            border_color: self.palette.border,
+        }
+    }
+}
+
+impl text_input::StyleSheet for Theme {
+    fn active(&self) -> text_input::Style {
+        let palette = self.palette();
+
+        text_input::Style {
+            background: Background::Color(palette.surface),
+            border_radius: 5.0,
+            border_width: self.border_width(),
+            border_color: palette.border,
+        }
+    }
+
+    fn focused(&self) -> text_input::Style {
+        text_input::Style {
+            border_color: self.palette().primary,
+            ..text_input::StyleSheet::active(self)
+        }
+    }
+
+    fn placeholder_color(&self) -> Color {
+        self.palette().border
+    }
+
+    fn value_color(&self) -> Color {
+        self.palette().text
+    }
+
+    fn selection_color(&self) -> Color {
+        Color {
+            a: 0.4,
+            ..self.palette().primary
+        }
+    }
+}
+
+impl slider::StyleSheet for Theme {
+    fn active(&self) -> slider::Style {
+        let palette = self.palette();
+
+        slider::Style {
+            rail_colors: (palette.border, palette.surface),
+            handle: slider::Handle {
+                shape: slider::HandleShape::Rectangle {
+                    width: 8,
+                    border_radius: 4.0,
+                },
+                color: palette.surface,
+                border_color: palette.border,
+                border_width: self.border_width(),
+            },
+        }
+    }
+
+    fn hovered(&self) -> slider::Style {
+        let active = slider::StyleSheet::active(self);
+
+        slider::Style {
+            handle: slider::Handle {
+                border_color: self.palette().primary,
+                ..active.handle
+            },
+            ..active
+        }
+    }
+
+    fn dragging(&self) -> slider::Style {
+        let active = slider::StyleSheet::active(self);
+
+        slider::Style {
+            handle: slider::Handle {
+                color: self.palette().primary,
+                ..active.handle
+            },
+            ..active
+        }
+    }
+}
+
+impl checkbox::StyleSheet for Theme {
+    fn active(&self, _is_checked: bool) -> checkbox::Style {
+        let palette = self.palette();
+
+        checkbox::Style {
+            background: Background::Color(palette.surface),
+            checkmark_color: palette.primary,
+            border_radius: 5.0,
+            border_width: self.border_width(),
+            border_color: palette.border,
+        }
+    }
+
+    fn hovered(&self, is_checked: bool) -> checkbox::Style {
+        checkbox::Style {
+            border_color: self.palette().primary,
+            ..checkbox::StyleSheet::active(self, is_checked)
+        }
+    }
+}
+
+impl radio::StyleSheet for Theme {
+    fn active(&self) -> radio::Style {
+        let palette = self.palette();
+
+        radio::Style {
+            background: Background::Color(palette.surface),
+            dot_color: palette.primary,
+            border_width: self.border_width(),
+            border_color: palette.border,
+        }
+    }
+
+    fn hovered(&self) -> radio::Style {
+        radio::Style {
+            border_color: self.palette().primary,
+            ..radio::StyleSheet::active(self)
+        }
+    }
+}
+
+impl scrollable::StyleSheet for Theme {
+    fn active(&self) -> scrollable::Scrollbar {
+        scrollable::Scrollbar {
+            background: None,
+            border_radius: 5.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+            scroller: scrollable::Scroller {
+                color: self.palette().border,
+                border_radius: 5.0,
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+        }
+    }
+
+    fn hovered(&self) -> scrollable::Scrollbar {
+        let active = scrollable::StyleSheet::active(self);
+
+        scrollable::Scrollbar {
+            background: Some(Background::Color(self.palette().surface)),
+            scroller: scrollable::Scroller {
+                color: self.palette().text,
+                ..active.scroller
+            },
+            ..active
+        }
+    }
+}
+
+impl progress_bar::StyleSheet for Theme {
+    fn style(&self) -> progress_bar::Style {
+        let palette = self.palette();
+
+        progress_bar::Style {
+            background: Background::Color(palette.surface),
+            bar: Background::Color(palette.primary),
+            border_radius: 4.0,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+use iced::{Align, Row};
+
+/// A row of controls, vertically centered and evenly spaced.
+pub fn toolbar<'a, Message>() -> Row<'a, Message> {
+    Row::new().spacing(10).align_items(Align::Center)
+}
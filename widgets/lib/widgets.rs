@@ -0,0 +1,14 @@
+//! Widgets shared by the tour and the example apps, so that they all look
+//! and size the same way.
+
+pub use crate::button::{button, icon_button, Size};
+pub use crate::card::card;
+pub use crate::input::labeled_input;
+pub use crate::theme::{ButtonStyle, CardStyle, Palette, Theme, Variant};
+pub use crate::toolbar::toolbar;
+
+mod button;
+mod card;
+mod input;
+mod theme;
+mod toolbar;